@@ -3,7 +3,6 @@ use quote::{quote, ToTokens};
 use std::convert::{TryFrom, TryInto};
 use std::iter;
 use syn::parse::{Parse, ParseStream, Result};
-use syn::punctuated;
 use syn::spanned::Spanned;
 use syn::{
     DeriveInput, Error, Field, GenericParam, Generics, Meta, MetaList, NestedMeta, Type, TypeParam,
@@ -14,16 +13,25 @@ struct PropField {
     ty: Type,
     name: Ident,
     wrapped_name: Option<Ident>,
+    into: bool,
 }
 
 impl TryFrom<Field> for PropField {
     type Error = Error;
 
     fn try_from(field: Field) -> Result<Self> {
+        let (required, into) = Self::props_flags(&field)?;
+        let name = field.ident.unwrap();
+        let wrapped_name = if required {
+            Some(Ident::new(&format!("{}_wrapper", name), Span::call_site()))
+        } else {
+            None
+        };
         Ok(PropField {
-            wrapped_name: Self::required_wrapper(&field)?,
+            wrapped_name,
+            into,
             ty: field.ty,
-            name: field.ident.unwrap(),
+            name,
         })
     }
 }
@@ -72,7 +80,7 @@ impl ToTokens for DerivePropsInput {
             ..
         } = self;
         let generic_params = &generics.params;
-        let generic_where = &generics.where_clause;
+        let (impl_generics, _, generic_where) = generics.split_for_impl();
         let generic_types = self.generic_types();
 
         let wrapped_name = Ident::new(&format!("Wrapped{}", props_name), Span::call_site());
@@ -88,14 +96,15 @@ impl ToTokens for DerivePropsInput {
         let builder_step_repeat = iter::repeat(&builder_step);
         let impl_builder_for_steps = self.impl_builder_for_steps(&builder_name, &builder_steps);
         let builder_set_fields = self.builder_set_fields();
+        let transform_fns = self.prop_fields.iter().map(|pf| pf.transform_fn(vis));
         let vis_repeat = iter::repeat(&vis);
 
         let expanded = quote! {
-            struct #wrapped_name#generics {
+            struct #wrapped_name#generics #generic_where {
                 #(#wrapped_field_defs)*
             }
 
-            impl#generics ::std::default::Default for #wrapped_name<#generic_types> #generic_where {
+            impl #impl_generics ::std::default::Default for #wrapped_name<#generic_types> #generic_where {
                 fn default() -> Self {
                     #wrapped_name::<#generic_types> {
                         #(#wrapped_default_setters)*
@@ -121,7 +130,7 @@ impl ToTokens for DerivePropsInput {
 
             #(#impl_builder_for_steps)*
 
-            impl #generics #builder_name<#builder_build_step, #generic_types> #generic_where {
+            impl #impl_generics #builder_name<#builder_build_step, #generic_types> #generic_where {
                 #[doc(hidden)]
                 #vis fn build(self) -> #props_name<#generic_types> {
                     #props_name::<#generic_types> {
@@ -130,7 +139,11 @@ impl ToTokens for DerivePropsInput {
                 }
             }
 
-            impl #generics ::yew::html::Properties for #props_name<#generic_types> #generic_where {
+            impl #impl_generics #props_name<#generic_types> #generic_where {
+                #(#transform_fns)*
+            }
+
+            impl #impl_generics ::yew::html::Properties for #props_name<#generic_types> #generic_where {
                 type Builder = #builder_name<#builder_start_step, #generic_types>;
 
                 fn builder() -> Self::Builder {
@@ -147,36 +160,82 @@ impl ToTokens for DerivePropsInput {
 }
 
 impl PropField {
-    fn required_wrapper(named_field: &syn::Field) -> Result<Option<Ident>> {
+    /// Reads `required` and `into` flags of `props` attribute.
+    fn props_flags(named_field: &syn::Field) -> Result<(bool, bool)> {
         let meta_list = if let Some(meta_list) = Self::find_props_meta_list(named_field) {
             meta_list
         } else {
-            return Ok(None);
+            return Ok((false, false));
         };
 
-        let expected_required = syn::Error::new(meta_list.span(), "expected `props(required)`");
-        let first_nested = if let Some(first_nested) = meta_list.nested.first() {
-            first_nested
-        } else {
-            return Err(expected_required);
-        };
+        let expected_flag = syn::Error::new(
+            meta_list.span(),
+            "expected `props(required)` or `props(into)`",
+        );
+        if meta_list.nested.is_empty() {
+            return Err(expected_flag);
+        }
 
-        let word_ident = match first_nested {
-            punctuated::Pair::End(NestedMeta::Meta(Meta::Word(ident))) => ident,
-            _ => return Err(expected_required),
-        };
+        let (mut required, mut into) = (false, false);
+        for nested in &meta_list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::Word(ident)) if ident == "required" => required = true,
+                NestedMeta::Meta(Meta::Word(ident)) if ident == "into" => into = true,
+                _ => return Err(expected_flag),
+            }
+        }
+        Ok((required, into))
+    }
 
-        if word_ident != "required" {
-            return Err(expected_required);
+    /// Generics, the type and the converted value of the setter argument.
+    /// Fields with `props(into)` accept anything which converts into them.
+    fn setter_arg(
+        &self,
+    ) -> (
+        proc_macro2::TokenStream,
+        proc_macro2::TokenStream,
+        proc_macro2::TokenStream,
+    ) {
+        let PropField { name, ty, into, .. } = self;
+        if *into {
+            (
+                quote! { <YEW_PROPS_INTO: ::std::convert::Into<#ty>> },
+                quote! { YEW_PROPS_INTO },
+                quote! { #name.into() },
+            )
+        } else {
+            (quote! {}, quote! { #ty }, quote! { #name })
         }
+    }
 
-        if let Some(ident) = &named_field.ident {
-            Ok(Some(Ident::new(
-                &format!("{}_wrapper", ident),
-                Span::call_site(),
-            )))
+    /// A function which `html!` uses to convert a value to the setter argument.
+    /// Setters of fields with `props(into)` are generic, `Transformer` can't
+    /// infer the target type for them, so their values are passed as is.
+    fn transform_fn(&self, vis: &Visibility) -> proc_macro2::TokenStream {
+        let PropField { name, ty, into, .. } = self;
+        let fn_name = Ident::new(&format!("__yew_transform_{}", name), Span::call_site());
+        if *into {
+            quote! {
+                #[doc(hidden)]
+                #vis fn #fn_name<YEW_COMP, YEW_FROM>(_: ::yew::virtual_dom::vcomp::ScopeHolder<YEW_COMP>, from: YEW_FROM) -> YEW_FROM
+                where
+                    YEW_COMP: ::yew::html::Component,
+                    YEW_FROM: ::std::convert::Into<#ty>,
+                {
+                    from
+                }
+            }
         } else {
-            unreachable!()
+            quote! {
+                #[doc(hidden)]
+                #vis fn #fn_name<YEW_COMP, YEW_FROM, YEW_TO>(scope: ::yew::virtual_dom::vcomp::ScopeHolder<YEW_COMP>, from: YEW_FROM) -> YEW_TO
+                where
+                    YEW_COMP: ::yew::html::Component,
+                    ::yew::virtual_dom::vcomp::VComp<YEW_COMP>: ::yew::virtual_dom::vcomp::Transformer<YEW_COMP, YEW_FROM, YEW_TO>,
+                {
+                    <::yew::virtual_dom::vcomp::VComp<YEW_COMP> as ::yew::virtual_dom::vcomp::Transformer<YEW_COMP, YEW_FROM, YEW_TO>>::transform(scope, from)
+                }
+            }
         }
     }
 
@@ -279,7 +338,7 @@ impl DerivePropsInput {
     ) -> proc_macro2::TokenStream {
         let Self { vis, generics, .. } = self;
         let generic_types = self.generic_types();
-        let generic_where = &generics.where_clause;
+        let (impl_generics, _, generic_where) = generics.split_for_impl();
 
         let mut fields_index = 0;
         let mut token_stream = proc_macro2::TokenStream::new();
@@ -298,15 +357,17 @@ impl DerivePropsInput {
                     required_field = Some(pf);
                     break;
                 } else {
-                    optional_fields.push((&pf.name, &pf.ty));
+                    optional_fields.push(pf);
                 }
             }
 
-            let optional_prop_fn = optional_fields.into_iter().map(|(prop_name, prop_type)| {
+            let optional_prop_fn = optional_fields.into_iter().map(|pf| {
+                let prop_name = &pf.name;
+                let (prop_generics, prop_type, prop_value) = pf.setter_arg();
                 quote! {
                     #[doc(hidden)]
-                    #vis fn #prop_name(mut self, #prop_name: #prop_type) -> #builder_name<#step_name, #generic_types> {
-                        self.wrapped.#prop_name = #prop_name;
+                    #vis fn #prop_name#prop_generics(mut self, #prop_name: #prop_type) -> #builder_name<#step_name, #generic_types> {
+                        self.wrapped.#prop_name = #prop_value;
                         self
                     }
                 }
@@ -314,14 +375,14 @@ impl DerivePropsInput {
 
             let required_prop_fn = required_field.iter().map(|p| {
                 let prop_name = &p.name;
-                let prop_type = &p.ty;
+                let (prop_generics, prop_type, prop_value) = p.setter_arg();
                 let wrapped_name = p.wrapped_name.as_ref().unwrap();
                 let next_step_name = &builder_step_names[step + 1];
 
                 quote! {
                     #[doc(hidden)]
-                    #vis fn #prop_name(mut self, #prop_name: #prop_type) -> #builder_name<#next_step_name, #generic_types> {
-                        self.wrapped.#wrapped_name = ::std::option::Option::Some(#prop_name);
+                    #vis fn #prop_name#prop_generics(mut self, #prop_name: #prop_type) -> #builder_name<#next_step_name, #generic_types> {
+                        self.wrapped.#wrapped_name = ::std::option::Option::Some(#prop_value);
                         #builder_name {
                             wrapped: self.wrapped,
                             _marker: ::std::marker::PhantomData,
@@ -331,7 +392,7 @@ impl DerivePropsInput {
             });
 
             token_stream.extend(quote! {
-                impl #generics #builder_name<#step_name, #generic_types> #generic_where {
+                impl #impl_generics #builder_name<#step_name, #generic_types> #generic_where {
                     #(#optional_prop_fn)*
                     #(#required_prop_fn)*
                 }
@@ -0,0 +1,335 @@
+use proc_macro2::{Ident, Span};
+use quote::{quote, ToTokens};
+use syn::parse::{Parse, ParseStream, Result};
+use syn::spanned::Spanned;
+use syn::{DeriveInput, Error, Fields, Lit, Meta, NestedMeta, Variant};
+
+enum Segment {
+    Static(String),
+    Capture(String),
+}
+
+enum RouteFields {
+    Unit,
+    Named(Vec<Ident>),
+    Unnamed(usize),
+}
+
+struct RouteVariant {
+    name: Ident,
+    fields: RouteFields,
+    segments: Vec<Segment>,
+    restore_scroll: bool,
+}
+
+impl RouteVariant {
+    fn from_variant(variant: Variant) -> Result<Self> {
+        let span = variant.span();
+        let (path, restore_scroll) = Self::find_path(&variant)?;
+        let segments: Vec<Segment> = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| {
+                if segment.starts_with('{') && segment.ends_with('}') {
+                    Segment::Capture(segment[1..segment.len() - 1].to_owned())
+                } else {
+                    Segment::Static(segment.to_owned())
+                }
+            })
+            .collect();
+        let captures = segments
+            .iter()
+            .filter(|segment| match segment {
+                Segment::Capture(_) => true,
+                Segment::Static(_) => false,
+            })
+            .count();
+
+        let fields = match variant.fields {
+            Fields::Unit => RouteFields::Unit,
+            Fields::Named(fields) => RouteFields::Named(
+                fields
+                    .named
+                    .into_iter()
+                    .map(|field| field.ident.unwrap())
+                    .collect(),
+            ),
+            Fields::Unnamed(fields) => RouteFields::Unnamed(fields.unnamed.len()),
+        };
+
+        let fields_len = match &fields {
+            RouteFields::Unit => 0,
+            RouteFields::Named(names) => {
+                for segment in &segments {
+                    if let Segment::Capture(capture) = segment {
+                        if !names.iter().any(|name| name == capture) {
+                            let msg = format!("the variant has no field `{}` to capture", capture);
+                            return Err(Error::new(span, msg));
+                        }
+                    }
+                }
+                names.len()
+            }
+            RouteFields::Unnamed(len) => *len,
+        };
+        if captures != fields_len {
+            return Err(Error::new(
+                span,
+                "every field of the variant has to be captured by the path",
+            ));
+        }
+
+        Ok(RouteVariant {
+            name: variant.ident,
+            fields,
+            segments,
+            restore_scroll,
+        })
+    }
+
+    /// Returns the path of the variant and whether its scroll is restored.
+    fn find_path(variant: &Variant) -> Result<(String, bool)> {
+        let expected_at = || Error::new(variant.span(), "expected `#[at(\"/path\")]` attribute");
+        for attr in &variant.attrs {
+            if let Ok(Meta::List(meta_list)) = attr.parse_meta() {
+                if meta_list.ident != "at" {
+                    continue;
+                }
+                let mut nested = meta_list.nested.iter();
+                let path = match nested.next() {
+                    Some(NestedMeta::Literal(Lit::Str(path))) => path.value(),
+                    _ => return Err(expected_at()),
+                };
+                let mut restore_scroll = true;
+                for option in nested {
+                    match option {
+                        NestedMeta::Meta(Meta::NameValue(option))
+                            if option.ident == "restore_scroll" =>
+                        {
+                            match &option.lit {
+                                Lit::Bool(value) => restore_scroll = value.value,
+                                lit => {
+                                    return Err(Error::new(
+                                        lit.span(),
+                                        "expected `true` or `false`",
+                                    ))
+                                }
+                            }
+                        }
+                        option => {
+                            return Err(Error::new(
+                                option.span(),
+                                "expected `restore_scroll = false`",
+                            ))
+                        }
+                    }
+                }
+                return Ok((path, restore_scroll));
+            }
+        }
+        Err(expected_at())
+    }
+
+    /// Indices of segments which are captured by fields in the order of fields.
+    fn captured_indices(&self) -> Vec<usize> {
+        let captures =
+            self.segments
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, segment)| match segment {
+                    Segment::Capture(capture) => Some((idx, capture)),
+                    Segment::Static(_) => None,
+                });
+        match &self.fields {
+            RouteFields::Unit => Vec::new(),
+            RouteFields::Unnamed(_) => captures.map(|(idx, _)| idx).collect(),
+            RouteFields::Named(names) => {
+                let captures: Vec<_> = captures.collect();
+                names
+                    .iter()
+                    .map(|name| {
+                        captures
+                            .iter()
+                            .find(|(_, capture)| name == capture.as_str())
+                            .map(|(idx, _)| *idx)
+                            .expect("field is not captured")
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    fn from_path_matcher(&self, routable_name: &Ident) -> proc_macro2::TokenStream {
+        let name = &self.name;
+        let len = self.segments.len();
+        let checks = self
+            .segments
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, segment)| match segment {
+                Segment::Static(value) => Some(quote! {
+                    if segments[#idx] != #value {
+                        return ::std::option::Option::None;
+                    }
+                }),
+                Segment::Capture(_) => None,
+            });
+        let values = self.captured_indices().into_iter().map(|idx| {
+            quote! { segments[#idx].parse().ok()? }
+        });
+        let construct = match &self.fields {
+            RouteFields::Unit => quote! { #routable_name::#name },
+            RouteFields::Named(names) => quote! { #routable_name::#name { #(#names: #values),* } },
+            RouteFields::Unnamed(_) => quote! { #routable_name::#name(#(#values),*) },
+        };
+        quote! {
+            let route = (|| -> ::std::option::Option<Self> {
+                if segments.len() != #len {
+                    return ::std::option::Option::None;
+                }
+                #(#checks)*
+                ::std::option::Option::Some(#construct)
+            })();
+            if route.is_some() {
+                return route;
+            }
+        }
+    }
+
+    fn to_path_arm(&self, routable_name: &Ident) -> proc_macro2::TokenStream {
+        let name = &self.name;
+        let mut format = String::new();
+        for segment in &self.segments {
+            format.push('/');
+            match segment {
+                Segment::Static(value) => {
+                    format.push_str(&value.replace('{', "{{").replace('}', "}}"));
+                }
+                Segment::Capture(_) => format.push_str("{}"),
+            }
+        }
+        if format.is_empty() {
+            format.push('/');
+        }
+        let bindings: Vec<Ident> = match &self.fields {
+            RouteFields::Unit => Vec::new(),
+            RouteFields::Named(names) => names.clone(),
+            RouteFields::Unnamed(len) => (0..*len)
+                .map(|idx| Ident::new(&format!("field_{}", idx), Span::call_site()))
+                .collect(),
+        };
+        // Arguments go in the order of captures in the path
+        let mut ordered: Vec<(usize, &Ident)> = self
+            .captured_indices()
+            .into_iter()
+            .zip(bindings.iter())
+            .collect();
+        ordered.sort_by_key(|(idx, _)| *idx);
+        let args = ordered.into_iter().map(|(_, binding)| binding);
+        let pattern = match &self.fields {
+            RouteFields::Unit => quote! { #routable_name::#name },
+            RouteFields::Named(_) => quote! { #routable_name::#name { #(#bindings),* } },
+            RouteFields::Unnamed(_) => quote! { #routable_name::#name(#(#bindings),*) },
+        };
+        quote! {
+            #pattern => format!(#format #(, #args)*),
+        }
+    }
+
+    fn restore_scroll_arm(&self, routable_name: &Ident) -> proc_macro2::TokenStream {
+        let name = &self.name;
+        let pattern = match &self.fields {
+            RouteFields::Unit => quote! { #routable_name::#name },
+            RouteFields::Named(_) => quote! { #routable_name::#name { .. } },
+            RouteFields::Unnamed(_) => quote! { #routable_name::#name(..) },
+        };
+        let restore_scroll = self.restore_scroll;
+        quote! {
+            #pattern => #restore_scroll,
+        }
+    }
+}
+
+pub struct DeriveRoutableInput {
+    routable_name: Ident,
+    variants: Vec<RouteVariant>,
+}
+
+impl Parse for DeriveRoutableInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let input: DeriveInput = input.parse()?;
+        if !input.generics.params.is_empty() {
+            return Err(Error::new(
+                input.generics.span(),
+                "generic routes are not supported",
+            ));
+        }
+        let variants = match input.data {
+            syn::Data::Enum(data) => data.variants,
+            _ => return Err(Error::new(input.ident.span(), "only enums can be routable")),
+        };
+
+        let variants = variants
+            .into_iter()
+            .map(RouteVariant::from_variant)
+            .collect::<Result<Vec<RouteVariant>>>()?;
+
+        Ok(Self {
+            routable_name: input.ident,
+            variants,
+        })
+    }
+}
+
+impl ToTokens for DeriveRoutableInput {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let routable_name = &self.routable_name;
+        let matchers = self
+            .variants
+            .iter()
+            .map(|variant| variant.from_path_matcher(routable_name));
+        let arms = self
+            .variants
+            .iter()
+            .map(|variant| variant.to_path_arm(routable_name));
+        // The default implementation is kept unless a route opts out
+        let restore_scroll = if self.variants.iter().all(|variant| variant.restore_scroll) {
+            None
+        } else {
+            let arms = self
+                .variants
+                .iter()
+                .map(|variant| variant.restore_scroll_arm(routable_name));
+            Some(quote! {
+                fn restore_scroll(&self) -> bool {
+                    match self {
+                        #(#arms)*
+                    }
+                }
+            })
+        };
+
+        let expanded = quote! {
+            impl ::yew::router::Routable for #routable_name {
+                fn from_path(path: &str) -> ::std::option::Option<Self> {
+                    let segments: ::std::vec::Vec<&str> = path
+                        .split('/')
+                        .filter(|segment| !segment.is_empty())
+                        .collect();
+                    #(#matchers)*
+                    ::std::option::Option::None
+                }
+
+                fn to_path(&self) -> ::std::string::String {
+                    match self {
+                        #(#arms)*
+                    }
+                }
+
+                #restore_scroll
+            }
+        };
+
+        tokens.extend(proc_macro2::TokenStream::from(expanded));
+    }
+}
@@ -1,5 +1,6 @@
 use super::HtmlProp;
 use super::HtmlPropSuffix;
+use super::HtmlTree;
 use crate::Peek;
 use boolinator::Boolinator;
 use proc_macro2::Span;
@@ -8,9 +9,12 @@ use syn::buffer::Cursor;
 use syn::parse;
 use syn::parse::{Parse, ParseStream, Result as ParseResult};
 use syn::spanned::Spanned;
-use syn::{Ident, Token, Type};
+use syn::{Expr, Ident, Token, Type};
 
-pub struct HtmlComponent(HtmlComponentInner);
+pub struct HtmlComponent {
+    inner: HtmlComponentInner,
+    children: Vec<HtmlTree>,
+}
 
 impl Peek<()> for HtmlComponent {
     fn peek(cursor: Cursor) -> Option<()> {
@@ -25,36 +29,111 @@ impl Parse for HtmlComponent {
     fn parse(input: ParseStream) -> ParseResult<Self> {
         let lt = input.parse::<Token![<]>()?;
         let HtmlPropSuffix { stream, div, gt } = input.parse()?;
-        if div.is_none() {
-            return Err(syn::Error::new_spanned(
-                HtmlComponentTag { lt, gt },
-                "expected component tag be of form `< .. />`",
-            ));
-        }
-
-        match parse(stream) {
-            Ok(comp) => Ok(HtmlComponent(comp)),
+        let inner: HtmlComponentInner = match parse(stream) {
+            Ok(inner) => inner,
             Err(err) => {
                 if err.to_string().starts_with("unexpected end of input") {
-                    Err(syn::Error::new_spanned(div, err.to_string()))
+                    let span = div.map(|div| div.spans[0]).unwrap_or(gt.spans[0]);
+                    return Err(syn::Error::new(span, err.to_string()));
                 } else {
-                    Err(err)
+                    return Err(err);
                 }
             }
+        };
+        if div.is_some() {
+            return Ok(HtmlComponent {
+                inner,
+                children: Vec::new(),
+            });
+        }
+
+        let mut children: Vec<HtmlTree> = vec![];
+        loop {
+            if input.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    HtmlComponentTag { lt, gt },
+                    "this open tag has no corresponding close tag",
+                ));
+            }
+            if HtmlComponentClose::peek(input.cursor()).is_some() {
+                break;
+            }
+            children.push(input.parse()?);
+        }
+
+        let close = input.parse::<HtmlComponentClose>()?;
+        let open_ty = &inner.ty;
+        let close_ty = &close.ty;
+        if quote! {#open_ty}.to_string() != quote! {#close_ty}.to_string() {
+            return Err(syn::Error::new_spanned(
+                close,
+                format!("expected close tag `</{}>`", quote! {#open_ty}),
+            ));
+        }
+        if !children.is_empty() {
+            if let Some(Props::With(WithProps(props))) = &inner.props {
+                return Err(syn::Error::new_spanned(
+                    props,
+                    "children can't be passed with `with props`",
+                ));
+            }
         }
+
+        Ok(HtmlComponent { inner, children })
     }
 }
 
 impl ToTokens for HtmlComponent {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        let HtmlComponentInner { ty, props } = &self.0;
         let vcomp_scope = Ident::new("__yew_vcomp_scope", Span::call_site());
+        let vchild = self.vchild_tokens();
+        tokens.extend(quote! {{
+            let #vcomp_scope: ::yew::virtual_dom::vcomp::ScopeHolder<_> = ::std::default::Default::default();
+            ::yew::virtual_dom::VNode::from(#vchild)
+        }});
+    }
+}
+
+impl HtmlComponent {
+    /// Tokens which build `VChild` of the component. Components in children
+    /// of another component are kept as `VChild` to let the parent read props.
+    fn vchild_tokens(&self) -> proc_macro2::TokenStream {
+        let HtmlComponentInner { ty, props } = &self.inner;
+        let vcomp_scope = Ident::new("__yew_vcomp_scope", Span::call_site());
+
+        let set_children = if self.children.is_empty() {
+            None
+        } else {
+            let len = self.children.len();
+            let children = self.children.iter().map(|child| match child {
+                HtmlTree::Component(comp) => {
+                    let vchild = comp.vchild_tokens();
+                    quote! {{
+                        let #vcomp_scope: ::yew::virtual_dom::vcomp::ScopeHolder<_> = ::std::default::Default::default();
+                        #vchild
+                    }.into()}
+                }
+                child => quote! { (#child).into() },
+            });
+            Some(quote! {
+                .children(::yew::html::ChildrenRenderer::new(#len, move || {
+                    let mut __yew_children = ::std::vec::Vec::with_capacity(#len);
+                    #(__yew_children.push(#children);)*
+                    __yew_children
+                }))
+            })
+        };
 
         let validate_props = if let Some(Props::List(ListProps(vec_props))) = props {
             let prop_ref = Ident::new("__yew_prop_ref", Span::call_site());
             let check_props = vec_props.iter().map(|HtmlProp { label, .. }| {
                 quote! { #prop_ref.#label; }
             });
+            let check_children = if self.children.is_empty() {
+                quote! {}
+            } else {
+                quote! { #prop_ref.children; }
+            };
 
             // This is a hack to avoid allocating memory but still have a reference to a props
             // struct so that attributes can be checked against it
@@ -72,6 +151,7 @@ impl ToTokens for HtmlComponent {
             quote! {
                 #unallocated_prop_ref
                 #(#check_props)*
+                #check_children
             }
         } else {
             quote! {}
@@ -80,23 +160,57 @@ impl ToTokens for HtmlComponent {
         let init_props = if let Some(props) = props {
             match props {
                 Props::List(ListProps(vec_props)) => {
-                    let set_props = vec_props.iter().map(|HtmlProp { label, value }| {
-                        quote_spanned! { value.span()=>
-                            .#label(<::yew::virtual_dom::vcomp::VComp<_> as ::yew::virtual_dom::vcomp::Transformer<_, _, _>>::transform(#vcomp_scope.clone(), #value))
-                        }
-                    });
+                    // Values are evaluated in the order of the source. Closures have no
+                    // side effects and are kept in place to infer types of their arguments.
+                    let temp_name = |i: usize, value: &Expr| match value {
+                        Expr::Closure(_) => None,
+                        _ => Some(Ident::new(&format!("__yew_prop_{}", i), value.span())),
+                    };
+                    let values =
+                        vec_props
+                            .iter()
+                            .enumerate()
+                            .filter_map(|(i, HtmlProp { value, .. })| {
+                                let temp = temp_name(i, value)?;
+                                Some(quote_spanned! { value.span()=> let #temp = #value; })
+                            });
+                    let mut set_props: Vec<(String, proc_macro2::TokenStream)> = vec_props
+                        .iter()
+                        .enumerate()
+                        .map(|(i, HtmlProp { label, value })| {
+                            let arg = match temp_name(i, value) {
+                                Some(temp) => quote! { #temp },
+                                None => quote! { #value },
+                            };
+                            // Properties know which fields are converted by setters
+                            let transform = Ident::new(&format!("__yew_transform_{}", label), label.span());
+                            let set_prop = quote_spanned! { value.span()=>
+                                .#label(<<#ty as ::yew::html::Component>::Properties>::#transform(#vcomp_scope.clone(), #arg))
+                            };
+                            (label.to_string(), set_prop)
+                        })
+                        .collect();
+                    // Builder of properties expects fields in alphabetical order
+                    if let Some(set_children) = set_children {
+                        set_props.push(("children".to_owned(), set_children));
+                    }
+                    set_props.sort_by(|a, b| a.0.cmp(&b.0));
+                    let set_props = set_props.into_iter().map(|(_, set_prop)| set_prop);
 
-                    quote! {
+                    quote! {{
+                        #(#values)*
                         <<#ty as ::yew::html::Component>::Properties as ::yew::html::Properties>::builder()
                             #(#set_props)*
                             .build()
-                    }
+                    }}
                 }
                 Props::With(WithProps(props)) => quote! { #props },
             }
         } else {
             quote! {
-                <<#ty as ::yew::html::Component>::Properties as ::yew::html::Properties>::builder().build()
+                <<#ty as ::yew::html::Component>::Properties as ::yew::html::Properties>::builder()
+                    #set_children
+                    .build()
             }
         };
 
@@ -109,22 +223,17 @@ impl ToTokens for HtmlComponent {
             }
         };
 
-        tokens.extend(quote! {{
+        quote! {{
             // Validation nevers executes at runtime
             if false {
                 #validate_comp
                 #validate_props
             }
 
-            let #vcomp_scope: ::yew::virtual_dom::vcomp::ScopeHolder<_> = ::std::default::Default::default();
-            ::yew::virtual_dom::VNode::VComp(
-                ::yew::virtual_dom::VComp::new::<#ty>(#init_props, #vcomp_scope)
-            )
-        }});
+            ::yew::virtual_dom::VChild::<#ty, _>::new(#init_props, #vcomp_scope.clone())
+        }}
     }
-}
 
-impl HtmlComponent {
     fn double_colon(mut cursor: Cursor) -> Option<Cursor> {
         for _ in 0..2 {
             let (punct, c) = cursor.punct()?;
@@ -192,6 +301,42 @@ impl Parse for HtmlComponentInner {
     }
 }
 
+struct HtmlComponentClose {
+    lt: Token![<],
+    div: Token![/],
+    ty: Type,
+    gt: Token![>],
+}
+
+impl Peek<()> for HtmlComponentClose {
+    fn peek(cursor: Cursor) -> Option<()> {
+        let (punct, cursor) = cursor.punct()?;
+        (punct.as_char() == '<').as_option()?;
+        let (punct, cursor) = cursor.punct()?;
+        (punct.as_char() == '/').as_option()?;
+
+        HtmlComponent::peek_type(cursor)
+    }
+}
+
+impl Parse for HtmlComponentClose {
+    fn parse(input: ParseStream) -> ParseResult<Self> {
+        Ok(HtmlComponentClose {
+            lt: input.parse()?,
+            div: input.parse()?,
+            ty: input.parse()?,
+            gt: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for HtmlComponentClose {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let HtmlComponentClose { lt, div, ty, gt } = self;
+        tokens.extend(quote! {#lt#div#ty#gt});
+    }
+}
+
 struct HtmlComponentTag {
     lt: Token![<],
     gt: Token![>],
@@ -216,6 +361,9 @@ enum Props {
 
 impl Peek<PropType> for Props {
     fn peek(cursor: Cursor) -> Option<PropType> {
+        if HtmlProp::peek(cursor).is_some() {
+            return Some(PropType::List);
+        }
         let (ident, _) = cursor.ident()?;
         let prop_type = if ident.to_string() == "with" {
             PropType::With
@@ -244,14 +392,6 @@ impl Parse for ListProps {
             }
         }
 
-        // alphabetize
-        props.sort_by(|a, b| {
-            a.label
-                .to_string()
-                .partial_cmp(&b.label.to_string())
-                .unwrap()
-        });
-
         Ok(ListProps(props))
     }
 }
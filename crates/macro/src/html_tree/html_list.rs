@@ -50,13 +50,12 @@ impl Parse for HtmlList {
 impl ToTokens for HtmlList {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let html_trees = &self.0;
-        tokens.extend(quote! {
-            ::yew::virtual_dom::VNode::VList(
-                ::yew::virtual_dom::vlist::VList {
-                    childs: vec![#(#html_trees,)*],
-                }
-            )
-        });
+        tokens.extend(quote! {{
+            #[allow(unused_mut)]
+            let mut __yew_vlist = ::yew::virtual_dom::vlist::VList::new();
+            #(__yew_vlist.add_child(#html_trees);)*
+            ::yew::virtual_dom::VNode::VList(__yew_vlist)
+        }});
     }
 }
 
@@ -0,0 +1,132 @@
+use super::HtmlTree;
+use crate::Peek;
+use boolinator::Boolinator;
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned, ToTokens};
+use syn::buffer::Cursor;
+use syn::parse::{Parse, ParseStream, Result as ParseResult};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{braced, token, Expr, Pat, Token};
+
+pub struct HtmlMatch {
+    match_token: Token![match],
+    expr: Expr,
+    brace: token::Brace,
+    arms: Vec<HtmlMatchArm>,
+}
+
+struct HtmlMatchArm {
+    pats: Punctuated<Pat, Token![|]>,
+    guard: Option<(Token![if], Expr)>,
+    fat_arrow: Token![=>],
+    body: ArmBody,
+}
+
+enum ArmBody {
+    Html(HtmlTree),
+    Expr(Expr),
+}
+
+impl Peek<()> for HtmlMatch {
+    fn peek(cursor: Cursor) -> Option<()> {
+        let (ident, _) = cursor.ident()?;
+        (ident.to_string() == "match").as_option()
+    }
+}
+
+impl Parse for HtmlMatch {
+    fn parse(input: ParseStream) -> ParseResult<Self> {
+        let match_token = input.parse::<Token![match]>()?;
+        if input.is_empty() {
+            return Err(syn::Error::new_spanned(
+                match_token,
+                "expected expression after `match`",
+            ));
+        }
+        let expr = Expr::parse_without_eager_brace(input)?;
+        if !input.peek(token::Brace) {
+            return Err(syn::Error::new_spanned(
+                match_token,
+                "expected arms in braces after the expression of `match`",
+            ));
+        }
+        let content;
+        let brace = braced!(content in input);
+        let mut arms = Vec::new();
+        while !content.is_empty() {
+            arms.push(content.parse()?);
+        }
+
+        Ok(HtmlMatch {
+            match_token,
+            expr,
+            brace,
+            arms,
+        })
+    }
+}
+
+impl Parse for HtmlMatchArm {
+    fn parse(input: ParseStream) -> ParseResult<Self> {
+        let _ = input.parse::<Option<Token![|]>>()?;
+        let pats = Punctuated::parse_separated_nonempty(input)?;
+        let guard = if input.peek(Token![if]) {
+            Some((input.parse()?, input.parse()?))
+        } else {
+            None
+        };
+        let fat_arrow = input.parse()?;
+        // Markup is written as is, anything else is an expression
+        // which converts into a node
+        let body = if HtmlTree::peek(input.cursor()).is_some() {
+            ArmBody::Html(input.parse()?)
+        } else {
+            ArmBody::Expr(input.parse()?)
+        };
+        let _ = input.parse::<Option<Token![,]>>()?;
+
+        Ok(HtmlMatchArm {
+            pats,
+            guard,
+            fat_arrow,
+            body,
+        })
+    }
+}
+
+impl ToTokens for HtmlMatch {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let HtmlMatch {
+            match_token,
+            expr,
+            brace,
+            arms,
+        } = self;
+
+        let arms = arms.iter().map(|arm| {
+            let HtmlMatchArm {
+                pats,
+                guard,
+                fat_arrow,
+                body,
+            } = arm;
+            let guard = guard
+                .as_ref()
+                .map(|(if_token, guard)| quote! { #if_token #guard });
+            let body = match body {
+                ArmBody::Html(html_tree) => quote! { #html_tree },
+                ArmBody::Expr(expr) => quote_spanned! {expr.span()=>
+                    ::yew::virtual_dom::VNode::from(#expr)
+                },
+            };
+            quote! { #pats #guard #fat_arrow #body, }
+        });
+
+        tokens.extend(quote_spanned! {brace.span=>
+            #match_token #expr {
+                #(#arms)*
+            }
+        });
+    }
+}
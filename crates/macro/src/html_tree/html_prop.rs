@@ -1,12 +1,12 @@
 use crate::Peek;
 use boolinator::Boolinator;
 use proc_macro::TokenStream;
-use proc_macro2::{Ident, TokenTree};
+use proc_macro2::{Delimiter, Ident, TokenTree};
 use quote::{quote, ToTokens};
 use std::fmt;
 use syn::buffer::Cursor;
 use syn::parse::{Parse, ParseStream, Result as ParseResult};
-use syn::{Expr, Token};
+use syn::{braced, Expr, ExprPath, Token};
 
 pub struct HtmlProp {
     pub label: HtmlPropLabel,
@@ -15,6 +15,9 @@ pub struct HtmlProp {
 
 impl Peek<()> for HtmlProp {
     fn peek(mut cursor: Cursor) -> Option<()> {
+        if HtmlProp::peek_shorthand(cursor).is_some() {
+            return Some(());
+        }
         loop {
             let (_, c) = cursor.ident()?;
             let (punct, c) = c.punct()?;
@@ -27,8 +30,34 @@ impl Peek<()> for HtmlProp {
     }
 }
 
+impl HtmlProp {
+    /// Peeks `{name}` which is a shorthand of `name=name`.
+    fn peek_shorthand(cursor: Cursor) -> Option<Ident> {
+        let (inner, _, _) = cursor.group(Delimiter::Brace)?;
+        let (ident, rest) = inner.ident()?;
+        rest.eof().as_option()?;
+        Some(ident)
+    }
+}
+
 impl Parse for HtmlProp {
     fn parse(input: ParseStream) -> ParseResult<Self> {
+        if HtmlProp::peek_shorthand(input.cursor()).is_some() {
+            let content;
+            braced!(content in input);
+            let name = content.parse::<Ident>()?;
+            let value = Expr::Path(ExprPath {
+                attrs: Vec::new(),
+                qself: None,
+                path: name.clone().into(),
+            });
+            // backwards compat
+            let _ = input.parse::<Token![,]>();
+            return Ok(HtmlProp {
+                label: HtmlPropLabel::new(name),
+                value,
+            });
+        }
         let label = input.parse::<HtmlPropLabel>()?;
         input.parse::<Token![=]>()?;
         let value = input.parse::<Expr>()?;
@@ -118,6 +147,8 @@ impl Parse for HtmlPropLabel {
             Ident::new("type", token.span).into()
         } else if let Ok(token) = input.parse::<Token![for]>() {
             Ident::new("for", token.span).into()
+        } else if let Ok(token) = input.parse::<Token![ref]>() {
+            Ident::new("ref", token.span).into()
         } else {
             input.parse::<Ident>()?.into()
         };
@@ -0,0 +1,391 @@
+//! Checks attributes of tags against known HTML attributes to catch typos.
+//! It's enabled by `strict_attributes` feature. Attributes with `data-`
+//! and `aria-` prefixes and attributes of unknown tags are not checked,
+//! so custom attributes can be written as `data-*`.
+
+use super::TagAttribute;
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use syn::parse::Result as ParseResult;
+use syn::Ident;
+
+const GLOBAL_ATTRIBUTES: &[&str] = &[
+    "accesskey",
+    "autocapitalize",
+    "contenteditable",
+    "dir",
+    "draggable",
+    "hidden",
+    "id",
+    "inputmode",
+    "is",
+    "lang",
+    "role",
+    "slot",
+    "spellcheck",
+    "style",
+    "tabindex",
+    "title",
+    "translate",
+];
+
+const UNCHECKED_PREFIXES: &[&str] = &["data-", "aria-"];
+
+lazy_static! {
+    static ref TAG_ATTRIBUTES: HashMap<&'static str, &'static [&'static str]> = {
+        let mut m: HashMap<&'static str, &'static [&'static str]> = HashMap::new();
+        for tag in &[
+            "abbr",
+            "address",
+            "article",
+            "aside",
+            "b",
+            "body",
+            "br",
+            "caption",
+            "code",
+            "dd",
+            "div",
+            "dl",
+            "dt",
+            "em",
+            "figcaption",
+            "figure",
+            "footer",
+            "h1",
+            "h2",
+            "h3",
+            "h4",
+            "h5",
+            "h6",
+            "head",
+            "header",
+            "hr",
+            "html",
+            "i",
+            "main",
+            "mark",
+            "nav",
+            "p",
+            "pre",
+            "s",
+            "section",
+            "small",
+            "span",
+            "strong",
+            "sub",
+            "summary",
+            "sup",
+            "table",
+            "tbody",
+            "tfoot",
+            "thead",
+            "title",
+            "tr",
+            "u",
+            "ul",
+            "wbr",
+        ] {
+            m.insert(*tag, &[]);
+        }
+        m.insert(
+            "a",
+            &[
+                "download",
+                "hreflang",
+                "ping",
+                "referrerpolicy",
+                "rel",
+                "target",
+            ],
+        );
+        m.insert(
+            "area",
+            &[
+                "alt",
+                "coords",
+                "download",
+                "hreflang",
+                "ping",
+                "referrerpolicy",
+                "rel",
+                "shape",
+                "target",
+            ],
+        );
+        m.insert(
+            "audio",
+            &[
+                "autoplay",
+                "controls",
+                "crossorigin",
+                "loop",
+                "muted",
+                "preload",
+                "src",
+            ],
+        );
+        m.insert("base", &["target"]);
+        m.insert("blockquote", &["cite"]);
+        m.insert(
+            "button",
+            &[
+                "autofocus",
+                "form",
+                "formaction",
+                "formenctype",
+                "formmethod",
+                "formnovalidate",
+                "formtarget",
+                "name",
+                "value",
+            ],
+        );
+        m.insert("canvas", &["height", "width"]);
+        m.insert("col", &["span"]);
+        m.insert("colgroup", &["span"]);
+        m.insert("data", &["value"]);
+        m.insert("del", &["cite", "datetime"]);
+        m.insert("details", &["open"]);
+        m.insert("dialog", &["open"]);
+        m.insert("embed", &["height", "src", "width"]);
+        m.insert("fieldset", &["form", "name"]);
+        m.insert(
+            "form",
+            &[
+                "accept-charset",
+                "action",
+                "autocomplete",
+                "enctype",
+                "method",
+                "name",
+                "novalidate",
+                "target",
+            ],
+        );
+        m.insert(
+            "iframe",
+            &[
+                "allow",
+                "allowfullscreen",
+                "height",
+                "name",
+                "referrerpolicy",
+                "sandbox",
+                "src",
+                "srcdoc",
+                "width",
+            ],
+        );
+        m.insert(
+            "img",
+            &[
+                "alt",
+                "crossorigin",
+                "decoding",
+                "height",
+                "ismap",
+                "loading",
+                "referrerpolicy",
+                "sizes",
+                "src",
+                "srcset",
+                "usemap",
+                "width",
+            ],
+        );
+        m.insert(
+            "input",
+            &[
+                "accept",
+                "alt",
+                "autocomplete",
+                "autofocus",
+                "capture",
+                "dirname",
+                "form",
+                "formaction",
+                "formenctype",
+                "formmethod",
+                "formnovalidate",
+                "formtarget",
+                "height",
+                "list",
+                "max",
+                "maxlength",
+                "min",
+                "minlength",
+                "multiple",
+                "name",
+                "pattern",
+                "placeholder",
+                "readonly",
+                "required",
+                "size",
+                "src",
+                "step",
+                "width",
+            ],
+        );
+        m.insert("ins", &["cite", "datetime"]);
+        m.insert("label", &["for", "form"]);
+        m.insert("li", &["value"]);
+        m.insert(
+            "link",
+            &[
+                "as",
+                "crossorigin",
+                "hreflang",
+                "integrity",
+                "media",
+                "referrerpolicy",
+                "rel",
+                "sizes",
+            ],
+        );
+        m.insert("meta", &["charset", "content", "http-equiv", "name"]);
+        m.insert(
+            "meter",
+            &["form", "high", "low", "max", "min", "optimum", "value"],
+        );
+        m.insert(
+            "object",
+            &["data", "form", "height", "name", "usemap", "width"],
+        );
+        m.insert("ol", &["reversed", "start"]);
+        m.insert("optgroup", &["label"]);
+        m.insert("option", &["label", "value"]);
+        m.insert("output", &["for", "form", "name", "value"]);
+        m.insert("param", &["name", "value"]);
+        m.insert("progress", &["max", "value"]);
+        m.insert("q", &["cite"]);
+        m.insert(
+            "script",
+            &[
+                "async",
+                "crossorigin",
+                "defer",
+                "integrity",
+                "nomodule",
+                "referrerpolicy",
+                "src",
+            ],
+        );
+        m.insert(
+            "select",
+            &[
+                "autocomplete",
+                "autofocus",
+                "form",
+                "multiple",
+                "name",
+                "required",
+                "size",
+                "value",
+            ],
+        );
+        m.insert("source", &["media", "sizes", "src", "srcset"]);
+        m.insert("style", &["media"]);
+        m.insert("td", &["colspan", "headers", "rowspan"]);
+        m.insert(
+            "textarea",
+            &[
+                "autocomplete",
+                "autofocus",
+                "cols",
+                "dirname",
+                "form",
+                "maxlength",
+                "minlength",
+                "name",
+                "placeholder",
+                "readonly",
+                "required",
+                "rows",
+                "wrap",
+            ],
+        );
+        m.insert("th", &["abbr", "colspan", "headers", "rowspan", "scope"]);
+        m.insert("time", &["datetime"]);
+        m.insert("track", &["default", "kind", "label", "src", "srclang"]);
+        m.insert(
+            "video",
+            &[
+                "autoplay",
+                "controls",
+                "crossorigin",
+                "height",
+                "loop",
+                "muted",
+                "playsinline",
+                "poster",
+                "preload",
+                "src",
+                "width",
+            ],
+        );
+        m
+    };
+}
+
+/// Returns an error for the first attribute which isn't known for the tag.
+pub fn check(tag: &Ident, attributes: &[TagAttribute]) -> ParseResult<()> {
+    let tag_name = tag.to_string();
+    let tag_attributes = match TAG_ATTRIBUTES.get(tag_name.as_str()) {
+        Some(tag_attributes) => tag_attributes,
+        None => return Ok(()),
+    };
+    for attribute in attributes {
+        let name = attribute.label.to_string();
+        if UNCHECKED_PREFIXES
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+        {
+            continue;
+        }
+        let mut known = GLOBAL_ATTRIBUTES.iter().chain(tag_attributes.iter());
+        if known.any(|known| *known == name) {
+            continue;
+        }
+        let mut message = format!("unknown attribute `{}` of `<{}>`", name, tag_name);
+        if let Some(similar) = similar_name(&name, tag_attributes) {
+            message.push_str(&format!(", did you mean `{}`?", similar));
+        }
+        message.push_str(" (use `data-` prefix for custom attributes)");
+        return Err(syn::Error::new_spanned(&attribute.label, message));
+    }
+    Ok(())
+}
+
+/// Finds a known attribute or listener which differs from `name` by one or two edits.
+fn similar_name(name: &str, tag_attributes: &[&'static str]) -> Option<&'static str> {
+    let listeners = super::tag_attributes::LISTENER_MAP.keys();
+    GLOBAL_ATTRIBUTES
+        .iter()
+        .chain(tag_attributes.iter())
+        .chain(listeners)
+        .map(|known| (distance(name, known), *known))
+        .filter(|(distance, _)| *distance <= 2)
+        .min()
+        .map(|(_, known)| known)
+}
+
+/// Levenshtein distance between two strings.
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                previous
+            } else {
+                1 + previous.min(row[j]).min(current)
+            };
+            previous = current;
+        }
+    }
+    row[b.len()]
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "strict_attributes")]
+mod known_attributes;
 mod tag_attributes;
 
 use super::HtmlProp as TagAttribute;
@@ -33,6 +35,7 @@ impl Parse for HtmlTag {
     fn parse(input: ParseStream) -> ParseResult<Self> {
         if HtmlTagClose::peek(input.cursor()).is_some() {
             return match input.parse::<HtmlTagClose>() {
+                Ok(close) if is_void(&close.ident) => Err(close.void_error()),
                 Ok(close) => Err(syn::Error::new_spanned(
                     close,
                     "this close tag has no corresponding open tag",
@@ -50,19 +53,46 @@ impl Parse for HtmlTag {
             });
         }
 
-        if !HtmlTag::verify_end(input.cursor(), &open.ident) {
-            return Err(syn::Error::new_spanned(
-                open,
-                "this open tag has no corresponding close tag",
-            ));
+        if is_void(&open.ident) {
+            if HtmlTag::verify_end(input.cursor(), &open.ident) {
+                if HtmlTagClose::peek(input.cursor()).is_some() {
+                    return Err(input.parse::<HtmlTagClose>()?.void_error());
+                }
+                return Err(syn::Error::new_spanned(
+                    open,
+                    format!("`{}` is a void element and can't have children", open.ident),
+                ));
+            }
+            return Ok(HtmlTag {
+                ident: open.ident,
+                attributes: open.attributes,
+                children: Vec::new(),
+            });
         }
 
         let mut children: Vec<HtmlTree> = vec![];
         loop {
+            if input.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    open,
+                    "this open tag has no corresponding close tag",
+                ));
+            }
             if let Some(next_close_ident) = HtmlTagClose::peek(input.cursor()) {
-                if open.ident.to_string() == next_close_ident.to_string() {
+                if open.ident == next_close_ident {
                     break;
                 }
+                let close = input.parse::<HtmlTagClose>()?;
+                if is_void(&close.ident) {
+                    return Err(close.void_error());
+                }
+                return Err(syn::Error::new_spanned(
+                    close,
+                    format!(
+                        "mismatched close tag: expected `</{}>`, found `</{}>`",
+                        open.ident, next_close_ident
+                    ),
+                ));
             }
 
             children.push(input.parse()?);
@@ -93,10 +123,12 @@ impl ToTokens for HtmlTag {
             attributes,
             kind,
             value,
+            bind,
             checked,
             disabled,
             selected,
             href,
+            node_ref,
             listeners,
         } = &attributes;
 
@@ -109,10 +141,20 @@ impl ToTokens for HtmlTag {
         let set_value = value.iter().map(|value| {
             quote_spanned! {value.span()=> #vtag.set_value(&(#value)); }
         });
+        let set_binding = bind.iter().map(|bind| {
+            quote_spanned! {bind.span()=>
+                let (__yew_bound_value, __yew_bound_handler) =
+                    ::yew::form::Binding::into_parts(#bind);
+                #vtag.set_value(&__yew_bound_value);
+                #vtag.add_listener(::std::boxed::Box::new(
+                    ::yew::html::oninput::Wrapper::from(__yew_bound_handler),
+                ));
+            }
+        });
         let add_href = href.iter().map(|href| {
             quote_spanned! {href.span()=>
                 let __yew_href: ::yew::html::Href = (#href).into();
-                #vtag.add_attribute("href", &__yew_href);
+                #vtag.add_static_attribute("href", &__yew_href);
             }
         });
         let set_checked = checked.iter().map(|checked| {
@@ -121,20 +163,26 @@ impl ToTokens for HtmlTag {
         let add_disabled = disabled.iter().map(|disabled| {
             quote_spanned! {disabled.span()=>
                 if #disabled {
-                    #vtag.add_attribute("disabled", &"true");
+                    #vtag.add_static_attribute("disabled", &"true");
                 }
             }
         });
         let add_selected = selected.iter().map(|selected| {
             quote_spanned! {selected.span()=>
                 if #selected {
-                    #vtag.add_attribute("selected", &"selected");
+                    #vtag.add_static_attribute("selected", &"selected");
                 }
             }
         });
+        let set_node_ref = node_ref.iter().map(|node_ref| {
+            quote_spanned! {node_ref.span()=>
+                let __yew_node_ref: ::yew::html::NodeRef = (#node_ref);
+                #vtag.node_ref = __yew_node_ref;
+            }
+        });
         let set_classes = classes.iter().map(|classes_form| match classes_form {
             ClassesForm::Tuple(classes) => quote! {
-                #vtag.add_classes(vec![#(&(#classes)),*]);
+                #(#vtag.add_class(&(#classes));)*
             },
             ClassesForm::Single(classes) => quote! {
                 #vtag.set_classes(&(#classes));
@@ -142,22 +190,37 @@ impl ToTokens for HtmlTag {
         });
 
         tokens.extend(quote! {{
+            #[allow(unused_mut)]
             let mut #vtag = ::yew::virtual_dom::vtag::VTag::new(#name);
             #(#set_kind)*
             #(#set_value)*
+            #(#set_binding)*
             #(#add_href)*
             #(#set_checked)*
             #(#add_disabled)*
             #(#add_selected)*
             #(#set_classes)*
-            #vtag.add_attributes(vec![#((#attr_labels.to_owned(), (#attr_values).to_string())),*]);
-            #vtag.add_listeners(vec![#(::std::boxed::Box::new(#listeners)),*]);
-            #vtag.add_children(vec![#(#children),*]);
+            #(#set_node_ref)*
+            // Sequential calls instead of nested `vec!` keep deep markup
+            // within the default recursion limit
+            #(#vtag.add_static_attribute(#attr_labels, &(#attr_values));)*
+            #(#vtag.add_listener(::std::boxed::Box::new(#listeners));)*
+            #(#vtag.add_child(#children);)*
             ::yew::virtual_dom::VNode::VTag(#vtag)
         }});
     }
 }
 
+/// Elements which can't have children or a close tag.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+fn is_void(ident: &Ident) -> bool {
+    VOID_ELEMENTS.contains(&ident.to_string().as_str())
+}
+
 impl HtmlTag {
     fn verify_end(mut cursor: Cursor, open_ident: &Ident) -> bool {
         let mut tag_stack_count = 1;
@@ -216,6 +279,12 @@ impl Parse for HtmlTagOpen {
         match ident.to_string().as_str() {
             "input" | "textarea" => {}
             _ => {
+                if let Some(bind) = &attributes.bind {
+                    return Err(syn::Error::new_spanned(
+                        bind,
+                        "`bind` attribute is only supported by `<input>` and `<textarea>`",
+                    ));
+                }
                 if let Some(value) = attributes.value.take() {
                     attributes.attributes.push(TagAttribute {
                         label: TagLabel::new(Ident::new("value", Span::call_site())),
@@ -225,6 +294,9 @@ impl Parse for HtmlTagOpen {
             }
         }
 
+        #[cfg(feature = "strict_attributes")]
+        known_attributes::check(&ident, &attributes.attributes)?;
+
         Ok(HtmlTagOpen {
             lt,
             ident,
@@ -278,6 +350,18 @@ impl Parse for HtmlTagClose {
     }
 }
 
+impl HtmlTagClose {
+    fn void_error(&self) -> syn::Error {
+        syn::Error::new_spanned(
+            self,
+            format!(
+                "`{}` is a void element and can't have a close tag",
+                self.ident
+            ),
+        )
+    }
+}
+
 impl ToTokens for HtmlTagClose {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let HtmlTagClose { lt, div, ident, gt } = self;
@@ -5,6 +5,7 @@ use proc_macro2::TokenStream;
 use quote::{quote, quote_spanned};
 use std::collections::HashMap;
 use syn::parse::{Parse, ParseStream, Result as ParseResult};
+use syn::spanned::Spanned;
 use syn::{Expr, ExprClosure, ExprTuple, Ident};
 
 pub struct TagAttributes {
@@ -12,11 +13,13 @@ pub struct TagAttributes {
     pub listeners: Vec<TokenStream>,
     pub classes: Option<ClassesForm>,
     pub value: Option<Expr>,
+    pub bind: Option<Expr>,
     pub kind: Option<Expr>,
     pub checked: Option<Expr>,
     pub disabled: Option<Expr>,
     pub selected: Option<Expr>,
     pub href: Option<Expr>,
+    pub node_ref: Option<Expr>,
 }
 
 pub enum ClassesForm {
@@ -31,7 +34,7 @@ pub struct TagListener {
 }
 
 lazy_static! {
-    static ref LISTENER_MAP: HashMap<&'static str, &'static str> = {
+    pub static ref LISTENER_MAP: HashMap<&'static str, &'static str> = {
         let mut m = HashMap::new();
         m.insert("onclick", "ClickEvent");
         m.insert("ondoubleclick", "DoubleClickEvent");
@@ -139,8 +142,9 @@ impl TagAttributes {
                     ));
                 }
 
-                let var = match inputs.first().unwrap().into_value() {
-                    syn::FnArg::Inferred(pat) => pat,
+                let (var, var_ty) = match inputs.first().unwrap().into_value() {
+                    syn::FnArg::Inferred(pat) => (pat, None),
+                    syn::FnArg::Captured(syn::ArgCaptured { pat, ty, .. }) => (pat, Some(ty)),
                     _ => return Err(syn::Error::new_spanned(or_span, "invalid closure argument")),
                 };
                 let handler =
@@ -153,7 +157,15 @@ impl TagAttributes {
                 };
                 let var_type = quote! { ::yew::events::#segment };
                 let wrapper_type = quote! { ::yew::html::#name::Wrapper };
+                // The closure always takes the expected event and a written type
+                // is checked separately to report a mismatch on the type itself
+                let check_type = var_ty.map(|ty| {
+                    quote_spanned! {ty.span()=>
+                        ::yew::html::#name::expect_event(::std::marker::PhantomData::<#ty>);
+                    }
+                });
                 let listener_stream = quote_spanned! {name.span()=> {
+                    #check_type
                     let #handler = move | #var: #var_type | #body;
                     let #listener = #wrapper_type::from(#handler);
                     #listener
@@ -176,6 +188,23 @@ impl Parse for TagAttributes {
             attributes.push(input.parse::<TagAttribute>()?);
         }
 
+        // A binding sets the value and listens to input itself
+        if let Some(bind) = attributes
+            .iter()
+            .find(|attr| attr.label.to_string() == "bind")
+        {
+            let conflicts = attributes.iter().any(|attr| {
+                let label = attr.label.to_string();
+                label == "value" || label == "oninput"
+            });
+            if conflicts {
+                return Err(syn::Error::new_spanned(
+                    &bind.label,
+                    "`bind` attribute can't be used with `value` or `oninput`",
+                ));
+            }
+        }
+
         let mut listeners = Vec::new();
         for listener in TagAttributes::drain_listeners(&mut attributes) {
             listeners.push(TagAttributes::map_listener(listener)?);
@@ -203,22 +232,26 @@ impl Parse for TagAttributes {
         let classes =
             TagAttributes::remove_attr(&mut attributes, "class").map(TagAttributes::map_classes);
         let value = TagAttributes::remove_attr(&mut attributes, "value");
+        let bind = TagAttributes::remove_attr(&mut attributes, "bind");
         let kind = TagAttributes::remove_attr(&mut attributes, "type");
         let checked = TagAttributes::remove_attr(&mut attributes, "checked");
         let disabled = TagAttributes::remove_attr(&mut attributes, "disabled");
         let selected = TagAttributes::remove_attr(&mut attributes, "selected");
         let href = TagAttributes::remove_attr(&mut attributes, "href");
+        let node_ref = TagAttributes::remove_attr(&mut attributes, "ref");
 
         Ok(TagAttributes {
             attributes,
             classes,
             listeners,
             value,
+            bind,
             kind,
             checked,
             disabled,
             selected,
             href,
+            node_ref,
         })
     }
 }
@@ -2,6 +2,7 @@ pub mod html_block;
 pub mod html_component;
 pub mod html_iterable;
 pub mod html_list;
+pub mod html_match;
 pub mod html_node;
 pub mod html_prop;
 pub mod html_tag;
@@ -11,6 +12,7 @@ use html_block::HtmlBlock;
 use html_component::HtmlComponent;
 use html_iterable::HtmlIterable;
 use html_list::HtmlList;
+use html_match::HtmlMatch;
 use html_node::HtmlNode;
 use html_prop::HtmlProp;
 use html_prop::HtmlPropLabel;
@@ -20,11 +22,13 @@ use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::buffer::Cursor;
 use syn::parse::{Parse, ParseStream, Result};
+use syn::Token;
 
 pub enum HtmlType {
     Block,
     Component,
     List,
+    Match,
     Tag,
     Empty,
 }
@@ -34,6 +38,7 @@ pub enum HtmlTree {
     Component(HtmlComponent),
     Iterable(HtmlIterable),
     List(HtmlList),
+    Match(HtmlMatch),
     Tag(HtmlTag),
     Node(HtmlNode),
     Empty,
@@ -51,6 +56,13 @@ impl Parse for HtmlRoot {
         };
 
         if !input.is_empty() {
+            // A stray close tag after the root gets a more precise error
+            if input.peek(Token![<])
+                && input.peek2(Token![/])
+                && HtmlTag::peek(input.cursor()).is_some()
+            {
+                input.parse::<HtmlTag>()?;
+            }
             let stream: TokenStream = input.parse()?;
             Err(syn::Error::new_spanned(
                 stream,
@@ -79,6 +91,7 @@ impl Parse for HtmlTree {
             HtmlType::Tag => HtmlTree::Tag(input.parse()?),
             HtmlType::Block => HtmlTree::Block(input.parse()?),
             HtmlType::List => HtmlTree::List(input.parse()?),
+            HtmlType::Match => HtmlTree::Match(input.parse()?),
         };
         Ok(html_tree)
     }
@@ -96,6 +109,8 @@ impl Peek<HtmlType> for HtmlTree {
             Some(HtmlType::Block)
         } else if HtmlList::peek(cursor).is_some() {
             Some(HtmlType::List)
+        } else if HtmlMatch::peek(cursor).is_some() {
+            Some(HtmlType::Match)
         } else {
             None
         }
@@ -110,6 +125,7 @@ impl ToTokens for HtmlTree {
             HtmlTree::Component(comp) => comp,
             HtmlTree::Tag(tag) => tag,
             HtmlTree::List(list) => list,
+            HtmlTree::Match(html_match) => html_match,
             HtmlTree::Node(node) => node,
             HtmlTree::Iterable(iterable) => iterable,
             HtmlTree::Block(block) => block,
@@ -1,6 +1,6 @@
 //! This crate provides Yew's procedural macro `html!` which allows using JSX-like syntax
-//! for generating html and the `Properties` derive macro for deriving the `Properties` trait
-//! for components.
+//! for generating html, the `Properties` derive macro for deriving the `Properties` trait
+//! for components and the `Routable` derive macro for mapping enums to paths.
 //!
 //! The `html!` macro uses [proc_macro_hack](https://github.com/dtolnay/proc-macro-hack) in order
 //! to be used in the expression position.
@@ -10,7 +10,7 @@
 //! use yew::prelude::*;
 //!
 //! # struct Component;
-//! #[derive(Properties)]
+//! #[derive(Clone, PartialEq, Properties)]
 //! struct Props {
 //!   #[props(required)]
 //!   prop: String,
@@ -57,9 +57,11 @@
 extern crate proc_macro;
 
 mod derive_props;
+mod derive_routable;
 mod html_tree;
 
 use derive_props::DerivePropsInput;
+use derive_routable::DeriveRoutableInput;
 use html_tree::HtmlRoot;
 use proc_macro::TokenStream;
 use proc_macro_hack::proc_macro_hack;
@@ -77,6 +79,12 @@ pub fn derive_props(input: TokenStream) -> TokenStream {
     TokenStream::from(input.into_token_stream())
 }
 
+#[proc_macro_derive(Routable, attributes(at))]
+pub fn derive_routable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveRoutableInput);
+    TokenStream::from(input.into_token_stream())
+}
+
 #[proc_macro_hack]
 pub fn html(input: TokenStream) -> TokenStream {
     let root = parse_macro_input!(input as HtmlRoot);
@@ -1,15 +1,26 @@
 //! This module contains types to support multi-threading in Yew.
 
 use crate::callback::Callback;
+use crate::format::{Binary, BincodeFormat, Format};
 use crate::scheduler::{scheduler, Runnable, Shared};
 use anymap::{AnyMap, Entry};
-use bincode;
+use failure::Error;
 use log::warn;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use slab::Slab;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+#[cfg(feature = "futures")]
+use std::future::Future;
 use std::marker::PhantomData;
+#[cfg(feature = "futures")]
+use std::pin::Pin;
 use std::rc::Rc;
+#[cfg(feature = "futures")]
+use std::task::{self, Poll, Waker};
+use stdweb::unstable::TryInto;
+use stdweb::web::TypedArray;
 use stdweb::Value;
 #[allow(unused_imports)]
 use stdweb::{_js_impl, js};
@@ -34,6 +45,10 @@ enum FromWorker<T> {
 impl<T> Transferable for FromWorker<T> where T: Serialize + for<'de> Deserialize<'de> {}
 
 /// Represents a message which you could send to an agent.
+///
+/// Messages are encoded with the format of the agent (`bincode` by default)
+/// and copied once from the wasm memory to an `ArrayBuffer`. The buffer is
+/// transferred to a worker, so `postMessage` doesn't copy it again.
 pub trait Transferable
 where
     Self: Serialize + for<'de> Deserialize<'de>,
@@ -41,22 +56,23 @@ where
 }
 
 trait Packed {
-    fn pack(&self) -> Vec<u8>;
-    fn unpack(data: &Vec<u8>) -> Self;
+    fn pack<AGN: Agent>(&self) -> TypedArray<u8>;
+    fn unpack<AGN: Agent>(data: &TypedArray<u8>) -> Self;
 }
 
 impl<T: Transferable> Packed for T {
-    fn pack(&self) -> Vec<u8> {
-        bincode::serialize(&self).expect("can't serialize a transferable object")
+    fn pack<AGN: Agent>(&self) -> TypedArray<u8> {
+        let data = AGN::encode(self).expect("can't serialize a transferable object");
+        data.as_slice().into()
     }
 
-    fn unpack(data: &Vec<u8>) -> Self {
-        bincode::deserialize(&data).expect("can't deserialize a transferable object")
+    fn unpack<AGN: Agent>(data: &TypedArray<u8>) -> Self {
+        AGN::decode(&data.to_vec()).expect("can't deserialize a transferable object")
     }
 }
 
 /// Id of responses handler.
-#[derive(Serialize, Deserialize, Eq, PartialEq, Hash, Clone, Copy)]
+#[derive(Serialize, Deserialize, Eq, PartialEq, Hash, Clone, Copy, Debug)]
 pub struct HandlerId(usize);
 
 impl From<usize> for HandlerId {
@@ -84,9 +100,18 @@ pub trait Threaded {
     fn register();
 }
 
+/// Reaches of agents which live in workers and are registered with `Threaded`.
+#[doc(hidden)]
+pub trait Remote: Discoverer {}
+
+impl Remote for Public {}
+
+impl Remote for Pool {}
+
 impl<T> Threaded for T
 where
-    T: Agent<Reach = Public>,
+    T: Agent,
+    T::Reach: Remote,
 {
     fn register() {
         let scope = AgentScope::<T>::new();
@@ -94,8 +119,8 @@ where
         let link = AgentLink::connect(&scope, responder);
         let upd = AgentUpdate::Create(link);
         scope.send(upd);
-        let handler = move |data: Vec<u8>| {
-            let msg = ToWorker::<T::Input>::unpack(&data);
+        let handler = move |data: TypedArray<u8>| {
+            let msg = ToWorker::<T::Input>::unpack::<T>(&data);
             match msg {
                 ToWorker::Connected(id) => {
                     let upd = AgentUpdate::Connected(id);
@@ -120,13 +145,14 @@ where
             }
         };
         let loaded: FromWorker<T::Output> = FromWorker::WorkerLoaded;
-        let loaded = loaded.pack();
+        let loaded = loaded.pack::<T>();
         js! {
             var handler = @{handler};
             self.onmessage = function(event) {
                 handler(event.data);
             };
-            self.postMessage(@{loaded});
+            var loaded = @{loaded};
+            self.postMessage(loaded, [loaded.buffer]);
         };
     }
 }
@@ -155,6 +181,151 @@ pub trait Bridge<AGN: Agent> {
     fn send(&mut self, msg: AGN::Input);
 }
 
+/// Id to correlate a response with a request.
+#[derive(Serialize, Deserialize, Eq, PartialEq, Hash, Clone, Copy, Debug)]
+pub struct RequestId(u64);
+
+/// An input of an agent which expects a response to a specific request.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Request<T> {
+    /// Id which the agent puts to the response.
+    pub id: RequestId,
+    /// Data of the request.
+    pub body: T,
+}
+
+impl<T> Transferable for Request<T> where T: Serialize + for<'de> Deserialize<'de> {}
+
+/// An output of an agent which answers a `Request` or is sent without a request.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Response<T> {
+    /// Id of the request or `None` for unsolicited updates.
+    pub id: Option<RequestId>,
+    /// Data of the response.
+    pub body: T,
+}
+
+impl<T> Transferable for Response<T> where T: Serialize + for<'de> Deserialize<'de> {}
+
+impl<T> Response<T> {
+    /// Creates a response to a request with the `id`.
+    pub fn to(id: RequestId, body: T) -> Self {
+        Response { id: Some(id), body }
+    }
+
+    /// Creates an update which is not related to any request.
+    pub fn update(body: T) -> Self {
+        Response { id: None, body }
+    }
+}
+
+/// A bridge which routes every response to the callback of its request.
+/// Use it with agents which take `Request` and return `Response`.
+pub struct RequestBridge<AGN: Agent> {
+    bridge: Box<dyn Bridge<AGN>>,
+    pending: Shared<HashMap<RequestId, Callback<AGN::Output>>>,
+    next_id: u64,
+}
+
+impl<AGN, OUT> RequestBridge<AGN>
+where
+    AGN: Bridged + Agent<Output = Response<OUT>>,
+{
+    /// Creates a bridge to an agent. Updates without a request and
+    /// responses to forgotten requests are sent to `updates`.
+    pub fn new(updates: Callback<AGN::Output>) -> Self {
+        let pending: Shared<HashMap<RequestId, Callback<AGN::Output>>> =
+            Rc::new(RefCell::new(HashMap::new()));
+        let requests = pending.clone();
+        let callback = move |response: AGN::Output| {
+            let callback = response.id.and_then(|id| requests.borrow_mut().remove(&id));
+            match callback {
+                Some(callback) => callback.emit(response),
+                None => updates.emit(response),
+            }
+        };
+        RequestBridge {
+            bridge: AGN::bridge(callback.into()),
+            pending,
+            next_id: 0,
+        }
+    }
+}
+
+impl<AGN, IN> RequestBridge<AGN>
+where
+    AGN: Agent<Input = Request<IN>>,
+{
+    /// Sends a request to an agent. The response with the same id is sent to `callback`.
+    pub fn request(&mut self, body: IN, callback: Callback<AGN::Output>) -> RequestId {
+        let id = RequestId(self.next_id);
+        self.next_id += 1;
+        self.pending.borrow_mut().insert(id, callback);
+        self.bridge.send(Request { id, body });
+        id
+    }
+
+    /// Forgets a pending request. Its response will be sent as an update.
+    pub fn forget(&mut self, id: RequestId) {
+        self.pending.borrow_mut().remove(&id);
+    }
+
+    /// Sends a request to an agent and returns a future of the response.
+    /// It's available with the `futures` feature only.
+    #[cfg(feature = "futures")]
+    pub fn request_future(&mut self, body: IN) -> ResponseFuture<AGN::Output>
+    where
+        AGN::Output: 'static,
+    {
+        let state = Rc::new(RefCell::new(ResponseState {
+            response: None,
+            waker: None,
+        }));
+        let on_response = state.clone();
+        let callback = move |response| {
+            let waker = {
+                let mut state = on_response.borrow_mut();
+                state.response = Some(response);
+                state.waker.take()
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        };
+        self.request(body, callback.into());
+        ResponseFuture { state }
+    }
+}
+
+#[cfg(feature = "futures")]
+struct ResponseState<T> {
+    response: Option<T>,
+    waker: Option<Waker>,
+}
+
+/// A future of a response to a request sent by `RequestBridge`.
+#[cfg(feature = "futures")]
+#[must_use]
+pub struct ResponseFuture<T> {
+    state: Shared<ResponseState<T>>,
+}
+
+#[cfg(feature = "futures")]
+impl<T> Future for ResponseFuture<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.borrow_mut();
+        match state.response.take() {
+            Some(response) => Poll::Ready(response),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
 // <<< SAME THREAD >>>
 
 struct LocalAgent<AGN: Agent> {
@@ -335,32 +506,22 @@ pub struct Private;
 
 impl Discoverer for Private {
     fn spawn_or_join<AGN: Agent>(callback: Callback<AGN::Output>) -> Box<dyn Bridge<AGN>> {
-        let handler = move |data: Vec<u8>| {
-            let msg = FromWorker::<AGN::Output>::unpack(&data);
+        let handler = move |data: TypedArray<u8>| {
+            let msg = FromWorker::<AGN::Output>::unpack::<AGN>(&data);
             match msg {
-                FromWorker::WorkerLoaded => {
-                    // TODO Send `Connected` message
-                }
+                FromWorker::WorkerLoaded => {}
                 FromWorker::ProcessOutput(id, output) => {
                     assert_eq!(id.raw_id(), SINGLETON_ID.raw_id());
                     callback.emit(output);
                 }
             }
         };
-        // TODO Need somethig better...
-        let name_of_resource = AGN::name_of_resource();
-        let worker = js! {
-            var worker = new Worker(@{name_of_resource});
-            var handler = @{handler};
-            worker.onmessage = function(event) {
-                handler(event.data);
-            };
-            return worker;
-        };
+        let worker = spawn_worker(AGN::name_of_resource(), handler);
         let bridge = PrivateBridge {
             worker,
             _agent: PhantomData,
         };
+        bridge.send_to_remote(ToWorker::Connected(SINGLETON_ID));
         Box::new(bridge)
     }
 }
@@ -371,24 +532,62 @@ pub struct PrivateBridge<T: Agent> {
     _agent: PhantomData<T>,
 }
 
+impl<AGN: Agent> PrivateBridge<AGN> {
+    fn send_to_remote(&self, msg: ToWorker<AGN::Input>) {
+        send_to_worker(&self.worker, msg.pack::<AGN>());
+    }
+}
+
 impl<AGN: Agent> Bridge<AGN> for PrivateBridge<AGN> {
     fn send(&mut self, msg: AGN::Input) {
-        // TODO Important! Implement.
-        // Use a queue to collect a messages if an instance is not ready
-        // and send them to an agent when it will reported readiness.
-        let msg = ToWorker::ProcessInput(SINGLETON_ID, msg).pack();
-        let worker = &self.worker;
-        js! {
-            var worker = @{worker};
-            var bytes = @{msg};
-            worker.postMessage(bytes);
-        };
+        let msg = ToWorker::ProcessInput(SINGLETON_ID, msg);
+        self.send_to_remote(msg);
     }
 }
 
 impl<AGN: Agent> Drop for PrivateBridge<AGN> {
     fn drop(&mut self) {
-        // TODO Send `Destroy` message.
+        self.send_to_remote(ToWorker::Disconnected(SINGLETON_ID));
+        self.send_to_remote(ToWorker::Destroy);
+    }
+}
+
+/// Starts a worker which sends received messages to `handler`.
+fn spawn_worker<F>(name_of_resource: &str, handler: F) -> Value
+where
+    F: Fn(TypedArray<u8>) + 'static,
+{
+    // The worker sends `WorkerLoaded` first. Messages posted before
+    // that will be lost, because the worker has no handler yet.
+    js! {
+        var worker = new Worker(@{name_of_resource});
+        var handler = @{handler};
+        worker.loaded = false;
+        worker.pending = [];
+        worker.onmessage = function(event) {
+            if (!worker.loaded) {
+                worker.loaded = true;
+                worker.pending.forEach(function(bytes) {
+                    worker.postMessage(bytes, [bytes.buffer]);
+                });
+                worker.pending = [];
+            }
+            handler(event.data);
+        };
+        return worker;
+    }
+}
+
+/// Posts a message to a worker or keeps it until the worker loaded.
+fn send_to_worker(worker: &Value, data: TypedArray<u8>) {
+    js! { @(no_return)
+        var worker = @{worker};
+        var bytes = @{data};
+        if (worker.loaded) {
+            worker.postMessage(bytes, [bytes.buffer]);
+        } else {
+            worker.pending.push(bytes);
+        }
     }
 }
 
@@ -407,11 +606,13 @@ impl<AGN: Agent> RemoteAgent<AGN> {
 
     fn create_bridge(&mut self, callback: Callback<AGN::Output>) -> PublicBridge<AGN> {
         let id = self.slab.borrow_mut().insert(callback);
-        PublicBridge {
+        let bridge = PublicBridge {
             worker: self.worker.clone(),
             id: id.into(),
             _agent: PhantomData,
-        }
+        };
+        bridge.send_to_remote(ToWorker::Connected(bridge.id));
+        bridge
     }
 
     fn remove_bridge(&mut self, bridge: &PublicBridge<AGN>) -> Last {
@@ -440,13 +641,10 @@ impl Discoverer for Public {
                     let slab_base: Shared<Slab<Callback<AGN::Output>>> =
                         Rc::new(RefCell::new(Slab::new()));
                     let slab = slab_base.clone();
-                    let handler = move |data: Vec<u8>| {
-                        let msg = FromWorker::<AGN::Output>::unpack(&data);
+                    let handler = move |data: TypedArray<u8>| {
+                        let msg = FromWorker::<AGN::Output>::unpack::<AGN>(&data);
                         match msg {
-                            FromWorker::WorkerLoaded => {
-                                // TODO Use `AtomicBool` lock to check its loaded
-                                // TODO Send `Connected` message
-                            }
+                            FromWorker::WorkerLoaded => {}
                             FromWorker::ProcessOutput(id, output) => {
                                 let callback = slab.borrow().get(id.raw_id()).cloned();
                                 if let Some(callback) = callback {
@@ -460,15 +658,7 @@ impl Discoverer for Public {
                             }
                         }
                     };
-                    let name_of_resource = AGN::name_of_resource();
-                    let worker = js! {
-                        var worker = new Worker(@{name_of_resource});
-                        var handler = @{handler};
-                        worker.onmessage = function(event) {
-                            handler(event.data);
-                        };
-                        return worker;
-                    };
+                    let worker = spawn_worker(AGN::name_of_resource(), handler);
                     let launched = RemoteAgent::new(&worker, slab_base);
                     entry.insert(launched).create_bridge(callback)
                 }
@@ -487,16 +677,7 @@ pub struct PublicBridge<T: Agent> {
 
 impl<AGN: Agent> PublicBridge<AGN> {
     fn send_to_remote(&self, msg: ToWorker<AGN::Input>) {
-        // TODO Important! Implement.
-        // Use a queue to collect a messages if an instance is not ready
-        // and send them to an agent when it will reported readiness.
-        let msg = msg.pack();
-        let worker = &self.worker;
-        js! {
-            var worker = @{worker};
-            var bytes = @{msg};
-            worker.postMessage(bytes);
-        };
+        send_to_worker(&self.worker, msg.pack::<AGN>());
     }
 }
 
@@ -528,6 +709,148 @@ impl<AGN: Agent> Drop for PublicBridge<AGN> {
     }
 }
 
+/// Create a pool of workers shared by bridges in the current thread.
+/// Every input is sent to the worker with the fewest jobs in progress, so
+/// every input is expected to get exactly one output. The size of the pool
+/// is set by `Agent::pool_size`. Workers are destroyed when the last bridge
+/// is dropped, after they finish the jobs which were sent before.
+pub struct Pool;
+
+impl Discoverer for Pool {
+    fn spawn_or_join<AGN: Agent>(callback: Callback<AGN::Output>) -> Box<dyn Bridge<AGN>> {
+        let bridge = WORKER_POOLS.with(|pools| {
+            let mut pools = pools.borrow_mut();
+            let pool = match pools.entry::<WorkerPool<AGN>>() {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(WorkerPool::new()),
+            };
+            pool.create_bridge(callback)
+        });
+        Box::new(bridge)
+    }
+}
+
+thread_local! {
+    static WORKER_POOLS: RefCell<AnyMap> = RefCell::new(AnyMap::new());
+}
+
+struct PoolWorker {
+    worker: Value,
+    jobs: Rc<Cell<usize>>,
+}
+
+struct WorkerPool<AGN: Agent> {
+    workers: Rc<Vec<PoolWorker>>,
+    slab: Shared<Slab<Callback<AGN::Output>>>,
+}
+
+impl<AGN: Agent> WorkerPool<AGN> {
+    fn new() -> Self {
+        let slab: Shared<Slab<Callback<AGN::Output>>> = Rc::new(RefCell::new(Slab::new()));
+        let workers = (0..AGN::pool_size().max(1))
+            .map(|_| {
+                let jobs = Rc::new(Cell::new(0));
+                let done = jobs.clone();
+                let slab = slab.clone();
+                let handler = move |data: TypedArray<u8>| {
+                    let msg = FromWorker::<AGN::Output>::unpack::<AGN>(&data);
+                    match msg {
+                        FromWorker::WorkerLoaded => {}
+                        FromWorker::ProcessOutput(id, output) => {
+                            done.set(done.get().saturating_sub(1));
+                            let callback = slab.borrow().get(id.raw_id()).cloned();
+                            if let Some(callback) = callback {
+                                callback.emit(output);
+                            } else {
+                                warn!(
+                                    "Id of handler for pooled worker not exists <slab>: {}",
+                                    id.raw_id()
+                                );
+                            }
+                        }
+                    }
+                };
+                let worker = spawn_worker(AGN::name_of_resource(), handler);
+                PoolWorker { worker, jobs }
+            })
+            .collect();
+        WorkerPool {
+            workers: Rc::new(workers),
+            slab,
+        }
+    }
+
+    fn create_bridge(&mut self, callback: Callback<AGN::Output>) -> PoolBridge<AGN> {
+        let id = self.slab.borrow_mut().insert(callback).into();
+        let bridge = PoolBridge {
+            workers: self.workers.clone(),
+            id,
+            _agent: PhantomData,
+        };
+        bridge.send_to_all(|| ToWorker::Connected(id));
+        bridge
+    }
+
+    fn remove_bridge(&mut self, bridge: &PoolBridge<AGN>) -> Last {
+        let mut slab = self.slab.borrow_mut();
+        let _ = slab.remove(bridge.id.raw_id());
+        slab.is_empty()
+    }
+}
+
+/// A connection manager for components interaction with a pool of workers.
+pub struct PoolBridge<T: Agent> {
+    workers: Rc<Vec<PoolWorker>>,
+    id: HandlerId,
+    _agent: PhantomData<T>,
+}
+
+impl<AGN: Agent> PoolBridge<AGN> {
+    fn send_to_all<F>(&self, msg: F)
+    where
+        F: Fn() -> ToWorker<AGN::Input>,
+    {
+        for worker in self.workers.iter() {
+            send_to_worker(&worker.worker, msg().pack::<AGN>());
+        }
+    }
+}
+
+impl<AGN: Agent> Bridge<AGN> for PoolBridge<AGN> {
+    fn send(&mut self, msg: AGN::Input) {
+        let worker = self
+            .workers
+            .iter()
+            .min_by_key(|worker| worker.jobs.get())
+            .expect("pool has no workers");
+        worker.jobs.set(worker.jobs.get() + 1);
+        let msg = ToWorker::ProcessInput(self.id, msg);
+        send_to_worker(&worker.worker, msg.pack::<AGN>());
+    }
+}
+
+impl<AGN: Agent> Drop for PoolBridge<AGN> {
+    fn drop(&mut self) {
+        WORKER_POOLS.with(|pools| {
+            let terminate_workers = {
+                if let Some(pool) = pools.borrow_mut().get_mut::<WorkerPool<AGN>>() {
+                    pool.remove_bridge(self)
+                } else {
+                    false
+                }
+            };
+            let id = self.id;
+            self.send_to_all(|| ToWorker::Disconnected(id));
+            if terminate_workers {
+                // Workers handle messages in order, so jobs
+                // which were sent before are finished
+                self.send_to_all(|| ToWorker::Destroy);
+                pools.borrow_mut().remove::<WorkerPool<AGN>>();
+            }
+        });
+    }
+}
+
 /// Create a single instance in a browser.
 pub struct Global;
 
@@ -567,17 +890,41 @@ pub trait Agent: Sized + 'static {
     fn name_of_resource() -> &'static str {
         "main.js"
     }
+
+    /// Encodes a message which is passed to or from a worker of the agent.
+    /// Override it together with `decode` to use another `Format`.
+    fn encode<T: Serialize>(value: &T) -> Binary {
+        BincodeFormat::to_vec(value)
+    }
+
+    /// Decodes a message which is passed to or from a worker of the agent.
+    fn decode<T: DeserializeOwned>(data: &[u8]) -> Result<T, Error> {
+        BincodeFormat::from_slice(data)
+    }
+
+    /// Returns the number of workers of an agent with `Pool` reach.
+    /// It's the number of logical processors by default.
+    fn pool_size() -> usize {
+        let size: f64 = js! {
+            return navigator.hardwareConcurrency || 4;
+        }
+        .try_into()
+        .unwrap_or(4.0);
+        size as usize
+    }
 }
 
 /// This sctruct holds a reference to a component and to a global scheduler.
 pub struct AgentScope<AGN: Agent> {
     shared_agent: Shared<AgentRunnable<AGN>>,
+    handlers: Shared<Vec<HandlerId>>,
 }
 
 impl<AGN: Agent> Clone for AgentScope<AGN> {
     fn clone(&self) -> Self {
         AgentScope {
             shared_agent: self.shared_agent.clone(),
+            handlers: self.handlers.clone(),
         }
     }
 }
@@ -585,12 +932,17 @@ impl<AGN: Agent> Clone for AgentScope<AGN> {
 impl<AGN: Agent> AgentScope<AGN> {
     fn new() -> Self {
         let shared_agent = Rc::new(RefCell::new(AgentRunnable::new()));
-        AgentScope { shared_agent }
+        let handlers = Rc::new(RefCell::new(Vec::new()));
+        AgentScope {
+            shared_agent,
+            handlers,
+        }
     }
 
     fn send(&self, update: AgentUpdate<AGN>) {
         let envelope = AgentEnvelope {
             shared_agent: self.shared_agent.clone(),
+            handlers: self.handlers.clone(),
             update,
         };
         let runnable: Box<dyn Runnable> = Box::new(envelope);
@@ -607,10 +959,10 @@ struct WorkerResponder {}
 impl<AGN: Agent> Responder<AGN> for WorkerResponder {
     fn response(&self, id: HandlerId, output: AGN::Output) {
         let msg = FromWorker::ProcessOutput(id, output);
-        let data = msg.pack();
+        let data = msg.pack::<AGN>();
         js! {
             var data = @{data};
-            self.postMessage(data);
+            self.postMessage(data, [data.buffer]);
         };
     }
 }
@@ -638,6 +990,21 @@ impl<AGN: Agent> AgentLink<AGN> {
         self.responder.response(id, output);
     }
 
+    /// Sends the output to every connected bridge.
+    pub fn broadcast(&self, output: AGN::Output)
+    where
+        AGN::Output: Clone,
+    {
+        for id in self.handlers() {
+            self.responder.response(id, output.clone());
+        }
+    }
+
+    /// Returns ids of connected bridges in the order of connection.
+    pub fn handlers(&self) -> Vec<HandlerId> {
+        self.scope.handlers.borrow().clone()
+    }
+
     /// This method sends messages back to the component's loop.
     pub fn send_back<F, IN>(&self, function: F) -> Callback<IN>
     where
@@ -679,6 +1046,7 @@ enum AgentUpdate<AGN: Agent> {
 
 struct AgentEnvelope<AGN: Agent> {
     shared_agent: Shared<AgentRunnable<AGN>>,
+    handlers: Shared<Vec<HandlerId>>,
     update: AgentUpdate<AGN>,
 }
 
@@ -702,6 +1070,7 @@ where
                     .update(msg);
             }
             AgentUpdate::Connected(id) => {
+                self.handlers.borrow_mut().push(id);
                 this.agent
                     .as_mut()
                     .expect("agent was not created to send a connected message")
@@ -714,6 +1083,7 @@ where
                     .handle(inp, id);
             }
             AgentUpdate::Disconnected(id) => {
+                self.handlers.borrow_mut().retain(|handler| *handler != id);
                 this.agent
                     .as_mut()
                     .expect("agent was not created to send a disconnected message")
@@ -2,8 +2,40 @@
 //! a component in an isolated scope.
 
 use crate::html::{Component, Renderable, Scope};
+use stdweb::unstable::TryFrom;
 use stdweb::web::{document, Element, INode, IParentNode};
 
+/// A handle of an app mounted to an element. Apps are independent:
+/// every app has its own scheduler, so renders of one app are flushed
+/// and dropped separately from others, and its listeners are delegated
+/// to the element it's mounted to.
+pub struct AppHandle<COMP: Component> {
+    scope: Scope<COMP>,
+    element: Element,
+}
+
+impl<COMP> AppHandle<COMP>
+where
+    COMP: Component + Renderable<COMP>,
+{
+    /// Returns the scope of the root component to send messages to it.
+    pub fn scope(&self) -> Scope<COMP> {
+        self.scope.clone()
+    }
+
+    /// Returns the element the app is mounted to.
+    pub fn element(&self) -> &Element {
+        &self.element
+    }
+
+    /// Destroys components of the app, removes their nodes and listeners
+    /// and drops their tasks. Renders which were scheduled are skipped.
+    /// Dropping the handle without this call keeps the app running.
+    pub fn destroy(mut self) {
+        self.scope.destroy_app();
+    }
+}
+
 /// An application instance.
 pub struct App<COMP: Component> {
     /// `Scope` holder
@@ -36,7 +68,25 @@ where
     /// will render the model to a virtual DOM tree.
     pub fn mount(self, element: Element) -> Scope<COMP> {
         clear_element(&element);
-        self.scope.mount_in_place(element, None, None, ())
+        self.scope.mount_app(element, ())
+    }
+
+    /// Mounts the app to the `element` and returns a handle to destroy it later.
+    pub fn mount_to_element(self, element: Element) -> AppHandle<COMP> {
+        let scope = self.mount(element.clone());
+        AppHandle { scope, element }
+    }
+
+    /// Mounts a separate app to every element which matches the `selector`.
+    /// Useful to embed widgets into a page rendered by a server.
+    pub fn mount_all(selector: &str) -> Vec<AppHandle<COMP>> {
+        document()
+            .query_selector_all(selector)
+            .expect("can't query elements to mount apps")
+            .iter()
+            .filter_map(|node| Element::try_from(node).ok())
+            .map(|element| App::<COMP>::new().mount_to_element(element))
+            .collect()
     }
 }
 
@@ -1,7 +1,10 @@
 //! This module contains structs to interact with `Scope`s.
 
+use crate::services::timeout::TimeoutService;
+use std::cell::RefCell;
 use std::fmt;
 use std::rc::Rc;
+use std::time::Duration;
 
 /// Universal callback wrapper.
 /// <aside class="warning">
@@ -56,4 +59,89 @@ impl<IN: 'static> Callback<IN> {
         };
         Callback::from(func)
     }
+
+    /// Changes input type of the callback like `reform`, but skips
+    /// the inputs for which `func` returns `None`.
+    pub fn filter_reform<F, T>(self, func: F) -> Callback<T>
+    where
+        F: Fn(T) -> Option<IN> + 'static,
+    {
+        let func = move |input| {
+            if let Some(output) = func(input) {
+                self.emit(output);
+            }
+        };
+        Callback::from(func)
+    }
+
+    /// Creates a callback from a function which could be called once only.
+    /// Emits after the first one are ignored.
+    pub fn once<F>(func: F) -> Self
+    where
+        F: FnOnce(IN) + 'static,
+    {
+        let func = RefCell::new(Some(func));
+        let func = move |input| {
+            let func = func.borrow_mut().take();
+            if let Some(func) = func {
+                func(input);
+            }
+        };
+        Callback::from(func)
+    }
+
+    /// Creates a callback which emits a value only when `duration` passed
+    /// since the last value. It's a shortcut to `TimeoutService::debounce`.
+    pub fn debounced(self, duration: Duration) -> Callback<IN> {
+        TimeoutService::new().debounce(duration, self)
+    }
+
+    /// Creates a callback which emits at most one value per `duration`.
+    /// It's a shortcut to `TimeoutService::throttle`.
+    pub fn throttled(self, duration: Duration) -> Callback<IN> {
+        TimeoutService::new().throttle(duration, self)
+    }
+}
+
+/// A callback which can be emitted once only. It takes `FnOnce` handlers,
+/// so values could be moved into the handler.
+pub struct CallbackOnce<IN>(Box<dyn FnOnce(IN)>);
+
+impl<IN, F: FnOnce(IN) + 'static> From<F> for CallbackOnce<IN> {
+    fn from(func: F) -> Self {
+        CallbackOnce(Box::new(func))
+    }
+}
+
+impl<IN> fmt::Debug for CallbackOnce<IN> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CallbackOnce<_>")
+    }
+}
+
+impl<IN> CallbackOnce<IN> {
+    /// This method calls the actual callback and consumes it.
+    pub fn emit(self, value: IN) {
+        (self.0)(value);
+    }
+}
+
+impl<IN: 'static> CallbackOnce<IN> {
+    /// Changes input type of the callback to another.
+    pub fn reform<F, T>(self, func: F) -> CallbackOnce<T>
+    where
+        F: FnOnce(T) -> IN + 'static,
+    {
+        let func = move |input| {
+            let output = func(input);
+            self.emit(output);
+        };
+        CallbackOnce::from(func)
+    }
+}
+
+impl<IN: 'static> From<CallbackOnce<IN>> for Callback<IN> {
+    fn from(callback: CallbackOnce<IN>) -> Self {
+        Callback::once(move |input| callback.emit(input))
+    }
 }
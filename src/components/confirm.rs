@@ -0,0 +1,87 @@
+//! This module contains implementation of `Confirm` component.
+//! It's a modal dialog with a message and two buttons which reports
+//! a choice of a user. `DialogService::confirm_async` opens it over
+//! the page, but it can be rendered as a usual component too:
+//!
+//! ```
+//! fn view() -> Html<Model> {
+//!     html! {
+//!         <Confirm message="Delete the file?" on_choice=|yes| Msg::Delete(yes) />
+//!     }
+//! }
+//! ```
+
+use crate::callback::Callback;
+use crate::html::{Component, ComponentLink, Html, Renderable, ShouldRender};
+use crate::macros::{html, Properties};
+
+/// `Confirm` component.
+pub struct Confirm {
+    props: Props,
+}
+
+/// Internal message of the component.
+pub enum Msg {
+    /// A user pressed a button.
+    Choose(bool),
+}
+
+/// Properties of `Confirm` component.
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    /// A question to a user.
+    pub message: String,
+    /// A label of the confirm button, `OK` by default.
+    pub confirm_label: Option<String>,
+    /// A label of the cancel button, `Cancel` by default.
+    pub cancel_label: Option<String>,
+    /// Gets `true` if a user confirmed and `false` otherwise.
+    #[props(required)]
+    pub on_choice: Callback<bool>,
+}
+
+impl Component for Confirm {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+        Confirm { props }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Choose(choice) => self.props.on_choice.emit(choice),
+        }
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props = props;
+        true
+    }
+}
+
+impl Renderable<Confirm> for Confirm {
+    fn view(&self) -> Html<Self> {
+        let confirm_label = self
+            .props
+            .confirm_label
+            .as_ref()
+            .map_or("OK", String::as_str);
+        let cancel_label = self
+            .props
+            .cancel_label
+            .as_ref()
+            .map_or("Cancel", String::as_str);
+        html! {
+            <div class="yew-dialog-backdrop"
+                 style="position: fixed; top: 0; right: 0; bottom: 0; left: 0; display: flex; align-items: center; justify-content: center; background: rgba(0, 0, 0, 0.4);">
+                <div class="yew-dialog" role="dialog" aria-modal="true">
+                    <p class="yew-dialog-message">{ &self.props.message }</p>
+                    <button class="yew-dialog-cancel" onclick=|_| Msg::Choose(false)>{ cancel_label }</button>
+                    <button class="yew-dialog-confirm" onclick=|_| Msg::Choose(true)>{ confirm_label }</button>
+                </div>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! This module contains implementation of `Head` component.
+//! It sets the title and tags of `<head>` while it's rendered
+//! and restores them when it's destroyed. Example:
+//!
+//! ```
+//! fn view() -> Html<Model> {
+//!     html! {
+//!         <>
+//!             <Head title="Profile" meta=vec![("description".into(), "User profile".into())] />
+//!             <Profile />
+//!         </>
+//!     }
+//! }
+//! ```
+//!
+//! Nested `Head` components override tags of their parents.
+
+use crate::html::{Component, ComponentLink, Html, Renderable, ShouldRender};
+use crate::macros::{html, Properties};
+use crate::services::document::{DocumentService, HeadTags, HeadTask};
+
+/// `Head` component.
+pub struct Head {
+    props: Props,
+    task: HeadTask,
+}
+
+/// Properties of `Head` component.
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    /// The title of the document.
+    pub title: Option<String>,
+    /// Pairs of `name` and `content` of `<meta>` tags.
+    pub meta: Vec<(String, String)>,
+    /// Pairs of `rel` and `href` of `<link>` tags.
+    pub links: Vec<(String, String)>,
+}
+
+impl Props {
+    fn to_tags(&self) -> HeadTags {
+        HeadTags {
+            title: self.title.clone(),
+            meta: self.meta.clone(),
+            links: self.links.clone(),
+        }
+    }
+}
+
+impl Component for Head {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+        let task = DocumentService::new().set_head(props.to_tags());
+        Head { props, task }
+    }
+
+    fn update(&mut self, _: Self::Message) -> ShouldRender {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if self.props != props {
+            self.task.update(props.to_tags());
+            self.props = props;
+        }
+        false
+    }
+}
+
+impl Renderable<Head> for Head {
+    fn view(&self) -> Html<Self> {
+        html! {}
+    }
+}
@@ -0,0 +1,187 @@
+//! This module contains implementation of `Lazy` component.
+//! It renders its children only when a loader reports that resources
+//! of the children are loaded, and renders a fallback until that moment.
+//! Components of the children are not created before. Example:
+//!
+//! ```
+//! fn view() -> Html<Model> {
+//!     html! {
+//!         <Lazy loader=lazy::script("charts.js") fallback=lazy::fallback(|| html! { <p>{ "Loading..." }</p> })>
+//!             <Chart data=self.data.clone() />
+//!         </Lazy>
+//!     }
+//! }
+//! ```
+//!
+//! Scripts are loaded once per URL, other `Lazy` components with the
+//! same script render their children immediately. Without a fallback
+//! `Lazy` suspends the closest `Suspense` while it's loading. If loading
+//! fails `error` children are rendered and the next `Lazy` with the same
+//! script tries to load it again.
+
+use crate::callback::Callback;
+use crate::components::suspense;
+use crate::html::{
+    Children, ChildrenRenderer, Component, ComponentLink, Html, Renderable, ShouldRender,
+};
+use crate::macros::{html, Properties};
+use failure::Fail;
+use std::cell::RefCell;
+use std::collections::HashMap;
+#[allow(unused_imports)]
+use stdweb::{_js_impl, js};
+
+/// A function which starts to load resources and calls the given callback
+/// when they are ready or failed.
+pub type Loader = Callback<Callback<Result<(), LoadError>>>;
+
+/// An error of loading resources. Contains the URL of a resource.
+#[derive(Debug, Clone, PartialEq, Fail)]
+#[fail(display = "can't load {}", _0)]
+pub struct LoadError(pub String);
+
+/// `Lazy` component.
+pub struct Lazy {
+    props: Props,
+    state: Option<Result<(), LoadError>>,
+}
+
+/// Internal message of the component.
+pub enum Msg {
+    /// Resources are loaded or failed.
+    Loaded(Result<(), LoadError>),
+}
+
+/// Properties of `Lazy` component.
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    /// Loads resources of children.
+    #[props(required)]
+    pub loader: Loader,
+    /// Rendered while resources are loading.
+    pub fallback: Children<Lazy>,
+    /// Rendered when resources are loaded.
+    pub children: Children<Lazy>,
+    /// Rendered when resources can't be loaded.
+    pub error: Children<Lazy>,
+}
+
+impl Component for Lazy {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, mut link: ComponentLink<Self>) -> Self {
+        props.loader.emit(link.send_back(Msg::Loaded));
+        Lazy { props, state: None }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Loaded(result) => {
+                self.state = Some(result);
+                true
+            }
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props = props;
+        true
+    }
+}
+
+impl Renderable<Lazy> for Lazy {
+    fn view(&self) -> Html<Self> {
+        match self.state {
+            Some(Ok(())) => self.props.children.render(),
+            Some(Err(_)) => self.props.error.render(),
+            None if self.props.fallback.is_empty() => suspense::pending(),
+            None => self.props.fallback.render(),
+        }
+    }
+}
+
+/// Creates a fallback of `Lazy` from a function which renders it.
+pub fn fallback<F>(render: F) -> Children<Lazy>
+where
+    F: Fn() -> Html<Lazy> + 'static,
+{
+    ChildrenRenderer::new(1, move || vec![render()])
+}
+
+enum ScriptState {
+    Loading(Vec<Callback<Result<(), LoadError>>>),
+    Loaded,
+}
+
+thread_local! {
+    static SCRIPTS: RefCell<HashMap<String, ScriptState>> = RefCell::new(HashMap::new());
+}
+
+/// Creates a loader which adds a `<script>` with the `url` to the document.
+/// A failed script is removed, so it's loaded again by the next loader.
+///
+/// The loader only waits for the script to be executed. To load another
+/// wasm module the script has to start it, the loader doesn't call
+/// an init function of JS glue code.
+pub fn script(url: &str) -> Loader {
+    let url = url.to_owned();
+    let loader = move |done: Callback<Result<(), LoadError>>| {
+        let (loaded, start) = SCRIPTS.with(|scripts| {
+            let mut scripts = scripts.borrow_mut();
+            match scripts.get_mut(&url) {
+                Some(ScriptState::Loaded) => (true, false),
+                Some(ScriptState::Loading(waiting)) => {
+                    waiting.push(done.clone());
+                    (false, false)
+                }
+                None => {
+                    scripts.insert(url.clone(), ScriptState::Loading(vec![done.clone()]));
+                    (false, true)
+                }
+            }
+        });
+        if loaded {
+            done.emit(Ok(()));
+        }
+        if start {
+            let loaded_url = url.clone();
+            let on_done = move |success: bool| {
+                let state = SCRIPTS.with(|scripts| {
+                    let mut scripts = scripts.borrow_mut();
+                    if success {
+                        scripts.insert(loaded_url.clone(), ScriptState::Loaded)
+                    } else {
+                        scripts.remove(&loaded_url)
+                    }
+                });
+                if let Some(ScriptState::Loading(waiting)) = state {
+                    let result = if success {
+                        Ok(())
+                    } else {
+                        Err(LoadError(loaded_url.clone()))
+                    };
+                    for done in waiting {
+                        done.emit(result.clone());
+                    }
+                }
+            };
+            js! { @(no_return)
+                var on_done = @{on_done};
+                var script = document.createElement("script");
+                script.src = @{&url};
+                script.onload = function() {
+                    on_done(true);
+                    on_done.drop();
+                };
+                script.onerror = function() {
+                    document.head.removeChild(script);
+                    on_done(false);
+                    on_done.drop();
+                };
+                document.head.appendChild(script);
+            }
+        }
+    };
+    loader.into()
+}
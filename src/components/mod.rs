@@ -1,6 +1,22 @@
 //! This module contains useful components.
-//! At this moment it includes typed `Select` only.
+//! At this moment it includes typed `Select`, memoizing `Pure`, `Head`
+//! to manage tags of the document's `<head>`, animating `Transition`,
+//! `Lazy` which loads resources of its children on demand, `Suspense`
+//! which shows a fallback while its content isn't ready and `Confirm`
+//! dialog.
 
+pub mod confirm;
+pub mod head;
+pub mod lazy;
+pub mod pure;
 pub mod select;
+pub mod suspense;
+pub mod transition;
 
+pub use self::confirm::Confirm;
+pub use self::head::Head;
+pub use self::lazy::Lazy;
+pub use self::pure::{Pure, PureComponent};
 pub use self::select::Select;
+pub use self::suspense::Suspense;
+pub use self::transition::Transition;
@@ -0,0 +1,65 @@
+//! This module contains implementation of `Pure` component.
+//! It wraps properties which know how to render themselves and
+//! skips rendering when properties are unchanged. Example:
+//!
+//! ```
+//! #[derive(Clone, PartialEq, Properties)]
+//! struct Greeting {
+//!     name: String,
+//! }
+//!
+//! impl PureComponent for Greeting {
+//!     fn render(&self) -> Html<Pure<Self>> {
+//!         html! {
+//!             <p>{ format!("Hello, {}!", self.name) }</p>
+//!         }
+//!     }
+//! }
+//!
+//! fn view() -> Html<Model> {
+//!     html! {
+//!         <Pure<Greeting> name="World" />
+//!     }
+//! }
+
+use crate::html::{Component, ComponentLink, Html, Properties, Renderable, ShouldRender};
+
+/// Properties which define a `Pure` component.
+pub trait PureComponent: Properties + Clone + PartialEq + Sized + 'static {
+    /// Renders the component from properties.
+    fn render(&self) -> Html<Pure<Self>>;
+}
+
+/// `Pure` component. It renders again only if new properties
+/// are not equal to the previous ones.
+pub struct Pure<T> {
+    props: T,
+}
+
+impl<T: PureComponent> Component for Pure<T> {
+    type Message = ();
+    type Properties = T;
+
+    fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+        Pure { props }
+    }
+
+    fn update(&mut self, _: Self::Message) -> ShouldRender {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if self.props == props {
+            false
+        } else {
+            self.props = props;
+            true
+        }
+    }
+}
+
+impl<T: PureComponent> Renderable<Pure<T>> for Pure<T> {
+    fn view(&self) -> Html<Self> {
+        self.props.render()
+    }
+}
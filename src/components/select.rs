@@ -31,7 +31,7 @@ pub enum Msg {
 }
 
 /// Properties of `Select` component.
-#[derive(PartialEq, Properties)]
+#[derive(Clone, PartialEq, Properties)]
 pub struct Props<T> {
     /// Initially selected value.
     pub selected: Option<T>,
@@ -0,0 +1,203 @@
+//! This module contains implementation of `Suspense` component.
+//! A component which isn't ready to render its content renders `pending()`
+//! instead, and the closest `Suspense` around it shows a fallback until all
+//! pending components inside it are ready. Example:
+//!
+//! ```
+//! impl Renderable<Profile> for Profile {
+//!     fn view(&self) -> Html<Self> {
+//!         match self.user {
+//!             Some(ref user) => html! { <p>{ &user.name }</p> },
+//!             None => suspense::pending(),
+//!         }
+//!     }
+//! }
+//!
+//! fn view() -> Html<Model> {
+//!     html! {
+//!         <Suspense fallback=suspense::fallback(|| html! { <p>{ "Loading..." }</p> })>
+//!             <Profile />
+//!             <Posts />
+//!         </Suspense>
+//!     }
+//! }
+//! ```
+//!
+//! Children are rendered hidden while the fallback is shown, so they
+//! keep working and can become ready.
+
+use crate::callback::Callback;
+use crate::html::{
+    Children, ChildrenRenderer, Component, ComponentLink, Html, NodeRef, Renderable, ShouldRender,
+};
+use crate::macros::{html, Properties};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+use stdweb::unstable::TryInto;
+#[allow(unused_imports)]
+use stdweb::{_js_impl, js};
+
+#[derive(Default)]
+struct Boundaries {
+    next_id: u64,
+    callbacks: HashMap<u64, Callback<bool>>,
+}
+
+thread_local! {
+    static BOUNDARIES: RefCell<Boundaries> = RefCell::new(Boundaries::default());
+}
+
+/// Notifies a boundary that a pending component was added or removed.
+fn notify(id: u64, suspend: bool) {
+    let callback = BOUNDARIES.with(|boundaries| boundaries.borrow().callbacks.get(&id).cloned());
+    if let Some(callback) = callback {
+        callback.emit(suspend);
+    }
+}
+
+/// `Suspense` component.
+pub struct Suspense {
+    props: Props,
+    id: u64,
+    pending: usize,
+}
+
+/// Internal message of `Suspense` component.
+pub enum Msg {
+    /// A pending component was rendered inside.
+    Suspend,
+    /// A pending component was removed.
+    Resume,
+}
+
+/// Properties of `Suspense` component.
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    /// Rendered while there are pending components inside.
+    pub fallback: Children<Suspense>,
+    /// Content which may have pending components.
+    pub children: Children<Suspense>,
+}
+
+impl Component for Suspense {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, mut link: ComponentLink<Self>) -> Self {
+        let callback = link.send_back(|suspend| if suspend { Msg::Suspend } else { Msg::Resume });
+        let id = BOUNDARIES.with(|boundaries| {
+            let mut boundaries = boundaries.borrow_mut();
+            let id = boundaries.next_id;
+            boundaries.next_id += 1;
+            boundaries.callbacks.insert(id, callback);
+            id
+        });
+        Suspense {
+            props,
+            id,
+            pending: 0,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        let was_pending = self.pending > 0;
+        match msg {
+            Msg::Suspend => self.pending += 1,
+            Msg::Resume => self.pending = self.pending.saturating_sub(1),
+        }
+        was_pending != (self.pending > 0)
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props = props;
+        true
+    }
+
+    fn destroy(&mut self) {
+        let id = self.id;
+        BOUNDARIES.with(|boundaries| boundaries.borrow_mut().callbacks.remove(&id));
+    }
+}
+
+impl Renderable<Suspense> for Suspense {
+    fn view(&self) -> Html<Self> {
+        let suspended = self.pending > 0;
+        let style = if suspended { "display: none;" } else { "" };
+        html! {
+            <>
+                <div data-yew-suspense=self.id style=style>
+                    { self.props.children.render() }
+                </div>
+                { if suspended { self.props.fallback.render() } else { html! {} } }
+            </>
+        }
+    }
+}
+
+/// Creates a fallback of `Suspense` from a function which renders it.
+pub fn fallback<F>(render: F) -> Children<Suspense>
+where
+    F: Fn() -> Html<Suspense> + 'static,
+{
+    ChildrenRenderer::new(1, move || vec![render()])
+}
+
+/// Renders a marker which suspends the closest `Suspense` while it's rendered.
+pub fn pending<COMP: Component>() -> Html<COMP> {
+    html! { <Pending /> }
+}
+
+/// A marker of content which isn't ready. Use `pending()` to render it.
+pub struct Pending {
+    node_ref: NodeRef,
+    boundary: Rc<Cell<Option<u64>>>,
+}
+
+impl Component for Pending {
+    type Message = ();
+    type Properties = ();
+
+    fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+        let node_ref = NodeRef::default();
+        let boundary = Rc::new(Cell::new(None));
+        let found = boundary.clone();
+        // The boundary is found from the DOM while the marker is mounted,
+        // so it suspends before the content is painted.
+        node_ref.when_set(move |node| {
+            let id: Option<f64> = js! {
+                var node = @{node};
+                var boundary = node.parentNode
+                    ? node.parentNode.closest("[data-yew-suspense]")
+                    : null;
+                return boundary ? Number(boundary.getAttribute("data-yew-suspense")) : null;
+            }
+            .try_into()
+            .unwrap_or(None);
+            if let Some(id) = id {
+                let id = id as u64;
+                notify(id, true);
+                found.set(Some(id));
+            }
+        });
+        Pending { node_ref, boundary }
+    }
+
+    fn update(&mut self, _: Self::Message) -> ShouldRender {
+        false
+    }
+
+    fn destroy(&mut self) {
+        if let Some(id) = self.boundary.take() {
+            notify(id, false);
+        }
+    }
+}
+
+impl Renderable<Pending> for Pending {
+    fn view(&self) -> Html<Self> {
+        html! {
+            <template ref=self.node_ref.clone()></template>
+        }
+    }
+}
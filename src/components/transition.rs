@@ -0,0 +1,227 @@
+//! This module contains implementation of `Transition` component.
+//! It animates its children when they are shown or hidden and keeps
+//! them in the DOM until the animation ends. Example:
+//!
+//! ```
+//! fn view() -> Html<Model> {
+//!     html! {
+//!         <Transition name="fade" show=self.visible timeout=Duration::from_millis(300)>
+//!             <div class="modal">{ "Hello" }</div>
+//!         </Transition>
+//!     }
+//! }
+//! ```
+//!
+//! Children are wrapped by a `<div>` which gets classes of a phase:
+//! `fade-enter` then `fade-enter fade-enter-active` when shown, and
+//! `fade-exit` then `fade-exit fade-exit-active` when hidden. A phase
+//! ends with `transitionend` or `animationend` event of the wrapper
+//! or when `timeout` elapsed.
+
+use crate::html::{Children, Component, ComponentLink, Html, NodeRef, Renderable, ShouldRender};
+use crate::macros::{html, Properties};
+use crate::services::render::{RenderService, RenderTask};
+use crate::services::timeout::{TimeoutService, TimeoutTask};
+use std::time::Duration;
+use stdweb::Value;
+#[allow(unused_imports)]
+use stdweb::{_js_impl, js};
+
+/// `Transition` component.
+pub struct Transition {
+    props: Props,
+    link: ComponentLink<Transition>,
+    phase: Phase,
+    node_ref: NodeRef,
+    frame: Option<RenderTask>,
+    timeout: Option<TimeoutTask>,
+    end_listener: Option<Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Phase {
+    Entering,
+    EnterActive,
+    Entered,
+    Exiting,
+    ExitActive,
+    Exited,
+}
+
+/// Internal message of the component.
+pub enum Msg {
+    /// The first frame of a phase was rendered with start classes.
+    Rendered,
+    /// The start classes were painted, the phase can be activated.
+    Frame,
+    /// The animation of a phase ended.
+    End,
+}
+
+/// Properties of `Transition` component.
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    /// Prefix of classes of phases.
+    #[props(required)]
+    pub name: String,
+    /// Shows children if `true`.
+    pub show: bool,
+    /// The longest duration of an animation.
+    #[props(required)]
+    pub timeout: Duration,
+    /// Animated children.
+    pub children: Children<Transition>,
+}
+
+impl Transition {
+    fn classes(&self) -> String {
+        let name = &self.props.name;
+        match self.phase {
+            Phase::Entering => format!("{}-enter", name),
+            Phase::EnterActive => format!("{0}-enter {0}-enter-active", name),
+            Phase::Exiting => format!("{}-exit", name),
+            Phase::ExitActive => format!("{0}-exit {0}-exit-active", name),
+            Phase::Entered | Phase::Exited => String::new(),
+        }
+    }
+
+    /// Starts a phase. Renders are deferred to an animation frame too,
+    /// so active classes are set a frame later than the start classes
+    /// are rendered, otherwise a browser never paints the start classes.
+    fn start(&mut self, phase: Phase) {
+        self.stop();
+        self.phase = phase;
+        self.next_frame(|| Msg::Rendered);
+    }
+
+    fn next_frame(&mut self, msg: fn() -> Msg) {
+        let callback = self.link.send_back(move |_| msg());
+        self.frame = Some(RenderService::new().request_animation_frame(callback));
+    }
+
+    fn stop(&mut self) {
+        self.frame.take();
+        self.timeout.take();
+        if let Some(listener) = self.end_listener.take() {
+            js! { @(no_return)
+                var listener = @{listener};
+                listener.active = false;
+                if (listener.node !== null) {
+                    listener.node.removeEventListener("transitionend", listener.handler);
+                    listener.node.removeEventListener("animationend", listener.handler);
+                }
+                listener.callback.drop();
+            }
+        }
+    }
+
+    fn listen_end(&mut self) {
+        let callback = self.link.send_back(|_| Msg::End);
+        self.timeout = Some(TimeoutService::new().spawn(self.props.timeout, callback.clone()));
+        let callback = move || callback.emit(());
+        let listener = js! {
+            var listener = { active: true, node: null, callback: @{callback} };
+            listener.handler = function(event) {
+                if (event.target === listener.node) {
+                    listener.callback();
+                }
+            };
+            return listener;
+        };
+        // The wrapper of hidden children is not rendered yet
+        let attach = listener.clone();
+        self.node_ref.when_set(move |node| {
+            js! { @(no_return)
+                var listener = @{attach};
+                var node = @{node};
+                if (listener.active) {
+                    listener.node = node;
+                    node.addEventListener("transitionend", listener.handler);
+                    node.addEventListener("animationend", listener.handler);
+                }
+            }
+        });
+        self.end_listener = Some(listener);
+    }
+}
+
+impl Component for Transition {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let phase = if props.show {
+            Phase::Entered
+        } else {
+            Phase::Exited
+        };
+        Transition {
+            props,
+            link,
+            phase,
+            node_ref: NodeRef::default(),
+            frame: None,
+            timeout: None,
+            end_listener: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match (msg, self.phase) {
+            (Msg::Rendered, Phase::Entering) | (Msg::Rendered, Phase::Exiting) => {
+                self.next_frame(|| Msg::Frame);
+                false
+            }
+            (Msg::Frame, Phase::Entering) => {
+                self.phase = Phase::EnterActive;
+                self.listen_end();
+                true
+            }
+            (Msg::Frame, Phase::Exiting) => {
+                self.phase = Phase::ExitActive;
+                self.listen_end();
+                true
+            }
+            (Msg::End, Phase::EnterActive) => {
+                self.stop();
+                self.phase = Phase::Entered;
+                true
+            }
+            (Msg::End, Phase::ExitActive) => {
+                self.stop();
+                self.phase = Phase::Exited;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if props.show != self.props.show {
+            if props.show {
+                self.start(Phase::Entering);
+            } else {
+                self.start(Phase::Exiting);
+            }
+        }
+        self.props = props;
+        true
+    }
+
+    fn destroy(&mut self) {
+        self.stop();
+    }
+}
+
+impl Renderable<Transition> for Transition {
+    fn view(&self) -> Html<Self> {
+        if self.phase == Phase::Exited {
+            return html! {};
+        }
+        html! {
+            <div class=self.classes() ref=self.node_ref.clone()>
+                { self.props.children.render() }
+            </div>
+        }
+    }
+}
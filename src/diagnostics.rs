@@ -0,0 +1,128 @@
+//! This module contains runtime diagnostics of common mistakes which
+//! are enabled by the `debug` feature. They print warnings to the console
+//! with the type name of the component:
+//!
+//! * a component renders too many times in one animation frame,
+//!   usually because `update` or `change` always returns `true` in a loop;
+//! * a message is sent to a component which was destroyed, usually
+//!   from a callback of a service task which wasn't dropped;
+//! * a listener is attached to an element which isn't inside of the element
+//!   the app is mounted to, so delegated events never reach it.
+//!
+//! Without the feature the checks are compiled out.
+
+#[cfg(feature = "debug")]
+use std::any::type_name;
+#[cfg(feature = "debug")]
+use std::cell::Cell;
+#[cfg(feature = "debug")]
+use stdweb::unstable::TryInto;
+use stdweb::web::Element;
+#[allow(unused_imports)]
+use stdweb::{_js_impl, js};
+
+#[cfg(feature = "debug")]
+thread_local! {
+    static FRAME: Cell<u64> = Cell::new(0);
+    static FRAME_REQUESTED: Cell<bool> = Cell::new(false);
+    static MAX_RENDERS: Cell<usize> = Cell::new(10);
+}
+
+/// Sets how many times a component can render in one animation frame
+/// before it's reported. It's 10 by default.
+#[cfg(feature = "debug")]
+pub fn set_max_renders_per_frame(limit: usize) {
+    MAX_RENDERS.with(|max| max.set(limit));
+}
+
+/// Returns the number of the current animation frame.
+#[cfg(feature = "debug")]
+fn current_frame() -> u64 {
+    if !FRAME_REQUESTED.with(|requested| requested.replace(true)) {
+        let next_frame = || {
+            FRAME.with(|frame| frame.set(frame.get() + 1));
+            FRAME_REQUESTED.with(|requested| requested.set(false));
+        };
+        js! { @(no_return)
+            var next_frame = @{next_frame};
+            requestAnimationFrame(function() {
+                next_frame();
+                next_frame.drop();
+            });
+        }
+    }
+    FRAME.with(Cell::get)
+}
+
+/// Counts renders of a component in the current animation frame.
+#[derive(Default)]
+pub(crate) struct RenderCounter {
+    #[cfg(feature = "debug")]
+    frame: u64,
+    #[cfg(feature = "debug")]
+    count: usize,
+}
+
+impl RenderCounter {
+    /// Reports the component once per frame if it renders too often.
+    pub(crate) fn rendered<COMP>(&mut self) {
+        #[cfg(feature = "debug")]
+        {
+            let frame = current_frame();
+            if self.frame != frame {
+                self.frame = frame;
+                self.count = 0;
+            }
+            self.count += 1;
+            let max = MAX_RENDERS.with(Cell::get);
+            if self.count == max + 1 {
+                warn(&format!(
+                    "{} rendered more than {} times in one frame, check that `update` and \
+                     `change` don't return `true` for every message in a loop",
+                    type_name::<COMP>(),
+                    max,
+                ));
+            }
+        }
+    }
+}
+
+/// Reports a message which was sent to a destroyed component.
+pub(crate) fn message_to_destroyed<COMP>() {
+    #[cfg(feature = "debug")]
+    warn(&format!(
+        "a message was sent to destroyed {}, drop tasks and bridges of \
+         the component when it's destroyed",
+        type_name::<COMP>(),
+    ));
+}
+
+/// Reports a listener which is attached to an element out of the root of the app.
+pub(crate) fn detached_listener<COMP>(element: &Element, root: Option<&Element>, kind: &str) {
+    #[cfg(feature = "debug")]
+    {
+        let connected: bool = js! {
+            var root = @{root};
+            return !root || root.contains(@{element});
+        }
+        .try_into()
+        .unwrap_or(true);
+        if !connected {
+            warn(&format!(
+                "`{}` listener of {} is attached to an element which isn't in \
+                 the root of the app, it won't get events",
+                kind,
+                type_name::<COMP>(),
+            ));
+        }
+    }
+    #[cfg(not(feature = "debug"))]
+    let _ = (element, root, kind);
+}
+
+#[cfg(feature = "debug")]
+fn warn(message: &str) {
+    js! { @(no_return)
+        console.warn(@{message});
+    }
+}
@@ -0,0 +1,400 @@
+//! This module contains helpers for forms. A `bind` attribute of
+//! `<input>` and `<textarea>` in `html!` binds the field two-way: it sets
+//! the value of the field and sends a message when a user changes it.
+//! The `bind!` macro creates a binding to a `String` field of a component,
+//! and all bound fields share one message variant:
+//!
+//! ```
+//! enum Msg {
+//!     Edit(Change<Model>),
+//! }
+//!
+//! impl Component for Model {
+//!     fn update(&mut self, msg: Self::Message) -> ShouldRender {
+//!         match msg {
+//!             Msg::Edit(change) => change.apply(self),
+//!         }
+//!         true
+//!     }
+//! }
+//!
+//! impl Renderable<Model> for Model {
+//!     fn view(&self) -> Html<Self> {
+//!         html! {
+//!             <form>
+//!                 <input bind=bind!(self.form.name, Msg::Edit) />
+//!                 <textarea bind=bind!(self.form.about, Msg::Edit) />
+//!             </form>
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! `FormState` keeps values of fields of a form as strings, tracks which
+//! of them were changed or visited, validates them and reads the values
+//! into a structure when the form is submitted:
+//!
+//! ```
+//! #[derive(Deserialize)]
+//! struct SignUp {
+//!     email: String,
+//!     age: u32,
+//! }
+//!
+//! let mut form = FormState::<SignUp>::new()
+//!     .validate("email", form::required)
+//!     .validate("age", |age| age.parse::<u32>().map(drop).map_err(|_| "enter a number".into()));
+//!
+//! html! {
+//!     <form onsubmit=|event| { event.prevent_default(); Msg::Submit }>
+//!         <input bind=form.bind("email", Msg::Form) onblur=|_| Msg::Touch("email") />
+//!         <p class="error">{ form.visible_error("email").unwrap_or_default() }</p>
+//!     </form>
+//! }
+//!
+//! // In `update`: `Msg::Form(change) => self.form.apply(change)`
+//! // and `Msg::Submit => if let Ok(sign_up) = self.form.submit() { ... }`
+//! ```
+
+use crate::html::InputData;
+use failure::Fail;
+use serde::de::value::{Error as ValueError, MapDeserializer};
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+use std::collections::{BTreeMap, HashSet};
+use std::marker::PhantomData;
+
+/// A value of a field and a function which makes a message from a new value.
+pub struct Binding<MSG> {
+    value: String,
+    update: Box<dyn Fn(String) -> MSG>,
+}
+
+impl<MSG: 'static> Binding<MSG> {
+    /// Creates a binding of the field with the `value`.
+    pub fn new<T, F>(value: T, update: F) -> Self
+    where
+        T: ToString,
+        F: Fn(String) -> MSG + 'static,
+    {
+        Binding {
+            value: value.to_string(),
+            update: Box::new(update),
+        }
+    }
+
+    /// Returns the current value of the field.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Used by `html!` to set the value and the `oninput` listener.
+    #[doc(hidden)]
+    pub fn into_parts(self) -> (String, impl Fn(InputData) -> MSG) {
+        let update = self.update;
+        (self.value, move |data: InputData| update(data.value))
+    }
+}
+
+/// A change of a model which is sent by a bound field. Use `apply`
+/// in `update` to write the new value into the model.
+pub struct Change<MODEL>(Box<dyn FnOnce(&mut MODEL)>);
+
+impl<MODEL> Change<MODEL> {
+    /// Creates a change from a function which writes into the model.
+    pub fn new<F>(change: F) -> Self
+    where
+        F: FnOnce(&mut MODEL) + 'static,
+    {
+        Change(Box::new(change))
+    }
+
+    /// Writes the change into the model.
+    pub fn apply(self, model: &mut MODEL) {
+        (self.0)(model)
+    }
+}
+
+/// Creates a `Binding` to a `String` field of a component which sends
+/// a `Change` of the component wrapped into the message.
+#[macro_export]
+macro_rules! bind {
+    ($this:ident . $($field:ident).+, $msg:expr) => {
+        $crate::form::Binding::new(&$this.$($field).+, move |value: ::std::string::String| {
+            $msg($crate::form::Change::new(move |model: &mut _| {
+                model.$($field).+ = value;
+            }))
+        })
+    };
+}
+
+/// A validator of a field. It returns a message for a user if the value is invalid.
+pub type Validator = Box<dyn Fn(&str) -> Result<(), String>>;
+
+/// A validator which rejects empty values.
+pub fn required(value: &str) -> Result<(), String> {
+    if value.trim().is_empty() {
+        Err("this field is required".to_owned())
+    } else {
+        Ok(())
+    }
+}
+
+/// A new value of a field which is sent by a binding of `FormState`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    /// The name of the field.
+    pub name: String,
+    /// The new value.
+    pub value: String,
+}
+
+/// Represents errors of submission of a form.
+#[derive(Debug, Fail, Clone, PartialEq)]
+pub enum FormError {
+    /// Some fields are invalid, the errors are kept by the form.
+    #[fail(display = "form has invalid fields")]
+    Invalid,
+    /// Values can't be read into the type of the form.
+    #[fail(display = "can't read values of the form: {}", _0)]
+    Deserialize(String),
+}
+
+/// A state of a form with values of type `T`.
+pub struct FormState<T> {
+    values: BTreeMap<String, String>,
+    initial: BTreeMap<String, String>,
+    touched: HashSet<String>,
+    errors: BTreeMap<String, String>,
+    validators: Vec<(String, Validator)>,
+    submitted: bool,
+    _type: PhantomData<T>,
+}
+
+impl<T: DeserializeOwned> Default for FormState<T> {
+    fn default() -> Self {
+        FormState {
+            values: BTreeMap::new(),
+            initial: BTreeMap::new(),
+            touched: HashSet::new(),
+            errors: BTreeMap::new(),
+            validators: Vec::new(),
+            submitted: false,
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<T: DeserializeOwned> FormState<T> {
+    /// Creates a form with empty fields.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets an initial value of a field. The field isn't dirty until
+    /// a user changes the value.
+    pub fn initial<V: ToString>(mut self, name: &str, value: V) -> Self {
+        let value = value.to_string();
+        self.initial.insert(name.to_owned(), value.clone());
+        self.values.insert(name.to_owned(), value);
+        self
+    }
+
+    /// Adds a validator of a field. A field can have many validators,
+    /// the first error is kept.
+    pub fn validate<F>(mut self, name: &str, validator: F) -> Self
+    where
+        F: Fn(&str) -> Result<(), String> + 'static,
+    {
+        self.validators.push((name.to_owned(), Box::new(validator)));
+        self
+    }
+
+    /// Creates a binding of the field for `bind` attribute of `html!`.
+    pub fn bind<MSG, F>(&self, name: &str, message: F) -> Binding<MSG>
+    where
+        MSG: 'static,
+        F: Fn(FieldChange) -> MSG + 'static,
+    {
+        let name = name.to_owned();
+        Binding::new(self.value(&name), move |value| {
+            message(FieldChange {
+                name: name.clone(),
+                value,
+            })
+        })
+    }
+
+    /// Writes a new value of a field and validates the field.
+    pub fn apply(&mut self, change: FieldChange) {
+        self.set(&change.name, change.value);
+    }
+
+    /// Sets a value of a field and validates the field.
+    pub fn set<V: ToString>(&mut self, name: &str, value: V) {
+        self.values.insert(name.to_owned(), value.to_string());
+        self.validate_field(name);
+    }
+
+    /// Marks a field as visited, usually on `onblur`. Errors of
+    /// visited fields are visible.
+    pub fn touch(&mut self, name: &str) {
+        self.touched.insert(name.to_owned());
+        self.validate_field(name);
+    }
+
+    /// Returns the current value of a field.
+    pub fn value(&self, name: &str) -> &str {
+        self.values.get(name).map_or("", String::as_str)
+    }
+
+    /// Returns `true` if the value of the field differs from the initial one.
+    pub fn is_dirty(&self, name: &str) -> bool {
+        self.value(name) != self.initial.get(name).map_or("", String::as_str)
+    }
+
+    /// Returns `true` if any field differs from its initial value.
+    pub fn is_any_dirty(&self) -> bool {
+        self.values.keys().any(|name| self.is_dirty(name))
+    }
+
+    /// Returns `true` if the field was visited.
+    pub fn is_touched(&self, name: &str) -> bool {
+        self.touched.contains(name)
+    }
+
+    /// Returns an error of the field even if a user hasn't visited it yet.
+    pub fn error(&self, name: &str) -> Option<&str> {
+        self.errors.get(name).map(String::as_str)
+    }
+
+    /// Returns an error of the field if it was visited or the form was submitted.
+    /// Use it to not show errors of fields a user hasn't filled yet.
+    pub fn visible_error(&self, name: &str) -> Option<&str> {
+        if self.submitted || self.is_touched(name) {
+            self.error(name)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if all fields are valid.
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns `true` if the form was submitted at least once.
+    pub fn is_submitted(&self) -> bool {
+        self.submitted
+    }
+
+    /// Validates all fields, makes all errors visible and reads the values
+    /// into `T`. Empty fields are read as `None` for `Option` fields.
+    pub fn submit(&mut self) -> Result<T, FormError> {
+        self.submitted = true;
+        let names: HashSet<String> = self
+            .validators
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in &names {
+            self.validate_field(name);
+        }
+        if !self.is_valid() {
+            return Err(FormError::Invalid);
+        }
+        let fields = self
+            .values
+            .iter()
+            .map(|(name, value)| (name.as_str(), FieldValue(value)));
+        T::deserialize(MapDeserializer::<_, ValueError>::new(fields))
+            .map_err(|err| FormError::Deserialize(err.to_string()))
+    }
+
+    /// Returns all fields to initial values and forgets errors and visits.
+    pub fn reset(&mut self) {
+        self.values = self.initial.clone();
+        self.touched.clear();
+        self.errors.clear();
+        self.submitted = false;
+    }
+
+    fn validate_field(&mut self, name: &str) {
+        let value = self.value(name);
+        let error = self
+            .validators
+            .iter()
+            .filter(|(field, _)| field == name)
+            .find_map(|(_, validator)| validator(value).err());
+        match error {
+            Some(error) => self.errors.insert(name.to_owned(), error),
+            None => self.errors.remove(name),
+        };
+    }
+}
+
+/// A raw value of a field which is parsed to the type of the field.
+struct FieldValue<'a>(&'a str);
+
+impl<'de, 'a> IntoDeserializer<'de, ValueError> for FieldValue<'a> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+macro_rules! parse_value {
+    ($($method:ident => $visit:ident,)*) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            let value = self.0.trim().parse().map_err(de::Error::custom)?;
+            visitor.$visit(value)
+        }
+    )*};
+}
+
+impl<'de, 'a> de::Deserializer<'de> for FieldValue<'a> {
+    type Error = ValueError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_str(self.0)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.0.trim().is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        // Unit variants are chosen by their names, e.g. in `<select>`
+        visitor.visit_enum(self.0.into_deserializer())
+    }
+
+    parse_value! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    forward_to_deserialize_any! {
+        str string bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
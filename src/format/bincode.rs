@@ -0,0 +1,21 @@
+//! Contains an implementation of bincode serialization format.
+
+use super::{Binary, Format};
+use bincode;
+use failure::Error;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// A codec of `bincode` format. Use it with `Formatted` wrapper.
+/// Agents encode messages with it by default.
+pub struct BincodeFormat;
+
+impl Format for BincodeFormat {
+    fn to_vec<T: Serialize>(value: &T) -> Binary {
+        bincode::serialize(value).map_err(Error::from)
+    }
+
+    fn from_slice<T: DeserializeOwned>(data: &[u8]) -> Result<T, Error> {
+        bincode::deserialize(data).map_err(Error::from)
+    }
+}
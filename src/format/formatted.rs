@@ -0,0 +1,80 @@
+//! Contains a wrapper to use any codec which implements `Format` trait.
+
+use super::{Binary, Format, Text};
+use failure::Error;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::marker::PhantomData;
+
+/// A representation of a data in a custom format. Use it as wrapper to
+/// set a codec you want to use for conversion:
+///
+/// ```rust
+/// // Converts (lazy) data with a codec
+/// let dump = Formatted::<MyCodec, _>::new(&data);
+///
+/// // Converts bytes to a data (lazy).
+/// let Formatted(data, _) = dump;
+/// ```
+///
+/// Text conversion works for codecs which produce UTF-8 only.
+pub struct Formatted<F, T>(pub T, pub PhantomData<F>);
+
+impl<F, T> Formatted<F, T> {
+    /// Wraps the data.
+    pub fn new(data: T) -> Self {
+        Formatted(data, PhantomData)
+    }
+
+    /// Returns the wrapped data.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<'a, F, T> Into<Binary> for Formatted<F, &'a T>
+where
+    F: Format,
+    T: Serialize,
+{
+    fn into(self) -> Binary {
+        F::to_vec(self.0)
+    }
+}
+
+impl<'a, F, T> Into<Text> for Formatted<F, &'a T>
+where
+    F: Format,
+    T: Serialize,
+{
+    fn into(self) -> Text {
+        let data = F::to_vec(self.0)?;
+        String::from_utf8(data).map_err(Error::from)
+    }
+}
+
+impl<F, T> From<Binary> for Formatted<F, Result<T, Error>>
+where
+    F: Format,
+    T: DeserializeOwned,
+{
+    fn from(value: Binary) -> Self {
+        match value {
+            Ok(data) => Formatted::new(F::from_slice(&data)),
+            Err(reason) => Formatted::new(Err(reason)),
+        }
+    }
+}
+
+impl<F, T> From<Text> for Formatted<F, Result<T, Error>>
+where
+    F: Format,
+    T: DeserializeOwned,
+{
+    fn from(value: Text) -> Self {
+        match value {
+            Ok(data) => Formatted::new(F::from_slice(data.as_bytes())),
+            Err(reason) => Formatted::new(Err(reason)),
+        }
+    }
+}
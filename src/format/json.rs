@@ -15,3 +15,5 @@ pub struct Json<T>(pub T);
 text_format!(Json based on serde_json);
 
 binary_format!(Json based on serde_json);
+
+format_codec!(JsonFormat based on serde_json);
@@ -49,3 +49,23 @@ macro_rules! binary_format {
         }
     };
 }
+
+macro_rules! format_codec {
+    ($codec:ident based on $format:ident) => {
+        /// A codec of the format. Use it with `Formatted` wrapper.
+        pub struct $codec;
+
+        impl $crate::format::Format for $codec {
+            fn to_vec<T: ::serde::Serialize>(value: &T) -> $crate::format::Binary {
+                $format::to_vec(value).map_err(::failure::Error::from)
+            }
+
+            fn from_slice<T>(data: &[u8]) -> Result<T, ::failure::Error>
+            where
+                T: ::serde::de::DeserializeOwned,
+            {
+                $format::from_slice(data).map_err(::failure::Error::from)
+            }
+        }
+    };
+}
@@ -5,12 +5,16 @@
 //! use `Into` and `From` traits to get (convert) the data.
 
 use failure::Error;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 
 #[macro_use]
 pub mod macros;
 
+pub mod bincode;
 #[cfg(feature = "cbor")]
 pub mod cbor;
+pub mod formatted;
 pub mod json;
 #[cfg(feature = "msgpack")]
 pub mod msgpack;
@@ -20,16 +24,18 @@ pub mod toml;
 #[cfg(feature = "yaml")]
 pub mod yaml;
 
+pub use self::bincode::BincodeFormat;
 #[cfg(feature = "cbor")]
-pub use self::cbor::Cbor;
-pub use self::json::Json;
+pub use self::cbor::{Cbor, CborFormat};
+pub use self::formatted::Formatted;
+pub use self::json::{Json, JsonFormat};
 #[cfg(feature = "msgpack")]
-pub use self::msgpack::MsgPack;
+pub use self::msgpack::{MsgPack, MsgPackFormat};
 pub use self::nothing::Nothing;
 #[cfg(feature = "toml")]
-pub use self::toml::Toml;
+pub use self::toml::{Toml, TomlFormat};
 #[cfg(feature = "yaml")]
-pub use self::yaml::Yaml;
+pub use self::yaml::{Yaml, YamlFormat};
 
 /// A representation of a value which can be stored and restored as a text.
 pub type Text = Result<String, Error>;
@@ -38,5 +44,17 @@ pub type Text = Result<String, Error>;
 pub type Binary = Result<Vec<u8>, Error>;
 
 /// A helper which represents a specific format.
+/// It was named `Format` before, the name is taken by the `Format` trait now.
 #[doc(hidden)]
-pub type Format<T> = Result<T, Error>;
+pub type Encoded<T> = Result<T, Error>;
+
+/// A serde-compatible codec. Implement it to plug a custom format into
+/// services with `Formatted` wrapper or into agents with `Agent::encode`
+/// and `Agent::decode`.
+pub trait Format {
+    /// Serializes a value to bytes.
+    fn to_vec<T: Serialize>(value: &T) -> Binary;
+
+    /// Deserializes a value from bytes.
+    fn from_slice<T: DeserializeOwned>(data: &[u8]) -> Result<T, Error>;
+}
@@ -17,3 +17,5 @@ pub struct Yaml<T>(pub T);
 text_format!(Yaml based on serde_yaml);
 
 binary_format!(Yaml based on serde_yaml);
+
+format_codec!(YamlFormat based on serde_yaml);
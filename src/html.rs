@@ -4,14 +4,20 @@
 //! to create own UI-components.
 
 use crate::callback::Callback;
-use crate::scheduler::{scheduler, Runnable, Shared};
-use crate::virtual_dom::{Listener, VDiff, VNode};
+use crate::diagnostics::{self, RenderCounter};
+use crate::scheduler::{Runnable, Scheduler, Shared};
+use crate::services::profiler::{self, Phase};
+use crate::virtual_dom::delegation::Delegation;
+use crate::virtual_dom::{Listener, ListenerHandle, VChild, VDiff, VList, VNode};
 use log::debug;
-use std::cell::RefCell;
+use std::cell::{Ref, RefCell};
 use std::fmt;
-use std::rc::Rc;
+#[cfg(feature = "futures")]
+use std::future::Future;
+use std::rc::{Rc, Weak};
+use stdweb::unstable::TryFrom;
 use stdweb::web::html_element::SelectElement;
-use stdweb::web::{Element, EventListenerHandle, FileList, INode, Node};
+use stdweb::web::{Element, FileList, INode, Node};
 #[allow(unused_imports)]
 use stdweb::{_js_impl, js};
 
@@ -30,6 +36,9 @@ pub trait Component: Sized + 'static {
     /// reference to a context.
     fn update(&mut self, msg: Self::Message) -> ShouldRender;
     /// This method called when properties changes, and once when component created.
+    /// A component rendered by a parent at the same position with the same type is
+    /// not created again, it gets new properties through this method instead if
+    /// they aren't equal to the previous ones.
     fn change(&mut self, _: Self::Properties) -> ShouldRender {
         unimplemented!("you should implement `change` method for a component with properties")
     }
@@ -71,6 +80,8 @@ pub trait Renderable<COMP: Component> {
 pub(crate) enum ComponentUpdate<COMP: Component> {
     /// Wraps messages for a component.
     Message(COMP::Message),
+    /// Wraps batch of messages for a component.
+    MessageBatch(Vec<COMP::Message>),
     /// Wraps properties for a component.
     Properties(COMP::Properties),
 }
@@ -108,12 +119,34 @@ where
     pub fn send_self(&mut self, msg: COMP::Message) {
         self.scope.send_message(msg);
     }
+
+    /// This method sends a batch of messages to this component.
+    /// All messages are handled by `update` before the component renders once.
+    pub fn send_message_batch(&mut self, msgs: Vec<COMP::Message>) {
+        self.scope.send_message_batch(msgs);
+    }
+
+    /// This method spawns a future and sends its output back to the component
+    /// when it resolves. It's available with the `futures` feature only.
+    #[cfg(feature = "futures")]
+    pub fn send_future<F>(&self, future: F)
+    where
+        F: Future<Output = COMP::Message> + 'static,
+    {
+        let mut scope = self.scope.clone();
+        let js_future = async move {
+            let msg = future.await;
+            scope.send_message(msg);
+        };
+        crate::scheduler::spawn_local(js_future);
+    }
 }
 
 enum ComponentState<COMP: Component> {
     Empty,
     Ready(ReadyState<COMP>),
     Created(CreatedState<COMP>),
+    Headless(HeadlessState<COMP>),
     Processing,
     Destroyed,
 }
@@ -124,6 +157,7 @@ impl<COMP: Component> fmt::Display for ComponentState<COMP> {
             ComponentState::Empty => "empty",
             ComponentState::Ready(_) => "ready",
             ComponentState::Created(_) => "created",
+            ComponentState::Headless(_) => "headless",
             ComponentState::Processing => "processing",
             ComponentState::Destroyed => "destroyed",
         };
@@ -148,6 +182,8 @@ impl<COMP: Component> ReadyState<COMP> {
             element: self.element,
             last_frame: self.ancestor,
             occupied: self.occupied,
+            render_scheduled: false,
+            renders: RenderCounter::default(),
         }
     }
 }
@@ -158,12 +194,19 @@ struct CreatedState<COMP: Component> {
     component: COMP,
     last_frame: Option<VNode<COMP>>,
     occupied: Option<NodeCell>,
+    render_scheduled: bool,
+    renders: RenderCounter,
 }
 
 impl<COMP: Component + Renderable<COMP>> CreatedState<COMP> {
     fn update(mut self) -> Self {
-        let mut next_frame = self.component.view();
-        let node = next_frame.apply(self.element.as_node(), None, self.last_frame, &self.env);
+        self.renders.rendered::<COMP>();
+        let component = &self.component;
+        let mut next_frame = profiler::measure::<COMP, _, _>(Phase::View, || component.view());
+        let (element, last_frame, env) = (&self.element, self.last_frame.take(), &self.env);
+        let node = profiler::measure::<COMP, _, _>(Phase::Patch, || {
+            next_frame.apply(element.as_node(), None, last_frame, env)
+        });
         if let Some(ref mut cell) = self.occupied {
             *cell.borrow_mut() = node;
         }
@@ -174,20 +217,44 @@ impl<COMP: Component + Renderable<COMP>> CreatedState<COMP> {
             last_frame: Some(next_frame),
             element: self.element,
             occupied: self.occupied,
+            render_scheduled: false,
+            renders: self.renders,
         }
     }
 }
 
+/// A component which isn't attached to the DOM. It keeps the last
+/// rendered frame which is never applied.
+struct HeadlessState<COMP: Component> {
+    component: COMP,
+    last_frame: VNode<COMP>,
+}
+
+/// Passes an update to the component and returns `true` if it has to be rendered.
+fn apply_update<COMP: Component>(component: &mut COMP, update: ComponentUpdate<COMP>) -> bool {
+    profiler::measure::<COMP, _, _>(Phase::Update, move || match update {
+        ComponentUpdate::Message(msg) => component.update(msg),
+        ComponentUpdate::MessageBatch(msgs) => msgs
+            .into_iter()
+            .fold(false, |acc, msg| component.update(msg) || acc),
+        ComponentUpdate::Properties(props) => component.change(props),
+    })
+}
+
 /// A context which contains a bridge to send a messages to a loop.
 /// Mostly services uses it.
 pub struct Scope<COMP: Component> {
     shared_state: Shared<ComponentState<COMP>>,
+    delegation: Delegation,
+    scheduler: Rc<Scheduler>,
 }
 
 impl<COMP: Component> Clone for Scope<COMP> {
     fn clone(&self) -> Self {
         Scope {
             shared_state: self.shared_state.clone(),
+            delegation: self.delegation.clone(),
+            scheduler: self.scheduler.clone(),
         }
     }
 }
@@ -199,7 +266,7 @@ where
     pub(crate) fn create(&mut self) {
         let shared_state = self.shared_state.clone();
         let create = CreateComponent { shared_state };
-        scheduler().put_and_try_run(Box::new(create));
+        self.scheduler.put_and_try_run(Box::new(create));
     }
 
     pub(crate) fn update(&mut self, update: ComponentUpdate<COMP>) {
@@ -207,19 +274,44 @@ where
             shared_state: self.shared_state.clone(),
             update,
         };
-        scheduler().put_and_try_run(Box::new(update));
+        self.scheduler.put_and_try_run(Box::new(update));
     }
 
+    /// Destroys the component and its children immediately. Children are
+    /// destroyed before their parents. If the component is processing now,
+    /// it's destroyed after that.
     pub(crate) fn destroy(&mut self) {
         let shared_state = self.shared_state.clone();
-        let destroy = DestroyComponent { shared_state };
-        scheduler().put_and_try_run(Box::new(destroy));
+        let destroy = Box::new(DestroyComponent { shared_state });
+        let processing = match *self.shared_state.borrow() {
+            ComponentState::Processing => true,
+            _ => false,
+        };
+        if processing {
+            self.scheduler.put_and_try_run(destroy);
+        } else {
+            destroy.run();
+        }
+    }
+
+    /// Destroys the root component of an app like `destroy`, removes
+    /// delegated listeners of the app from the element it's mounted to
+    /// and drops renders of the app which weren't flushed yet.
+    pub(crate) fn destroy_app(&mut self) {
+        self.destroy();
+        self.delegation.detach();
+        self.scheduler.clear_renders();
     }
 
     /// Send a message to the component
     pub fn send_message(&mut self, msg: COMP::Message) {
         self.update(ComponentUpdate::Message(msg));
     }
+
+    /// Send a batch of messages to the component
+    pub fn send_message_batch(&mut self, msgs: Vec<COMP::Message>) {
+        self.update(ComponentUpdate::MessageBatch(msgs));
+    }
 }
 
 /// Holder for the element.
@@ -231,7 +323,32 @@ where
 {
     pub(crate) fn new() -> Self {
         let shared_state = Rc::new(RefCell::new(ComponentState::Empty));
-        Scope { shared_state }
+        let delegation = Delegation::default();
+        let scheduler = Rc::new(Scheduler::new());
+        Scope {
+            shared_state,
+            delegation,
+            scheduler,
+        }
+    }
+
+    /// Creates a scope of a child component which belongs to the same app.
+    pub(crate) fn child_of<PARENT: Component>(parent: &Scope<PARENT>) -> Self {
+        let shared_state = Rc::new(RefCell::new(ComponentState::Empty));
+        let delegation = parent.delegation.clone();
+        let scheduler = parent.scheduler.clone();
+        Scope {
+            shared_state,
+            delegation,
+            scheduler,
+        }
+    }
+
+    /// Mounts the component as the root of an app to the `element`.
+    /// Listeners of the app are delegated to the `element`.
+    pub(crate) fn mount_app(self, element: Element, props: COMP::Properties) -> Scope<COMP> {
+        self.delegation.set_root(&element);
+        self.mount_in_place(element, None, None, props)
     }
 
     // TODO Consider to use &Node instead of Element as parent
@@ -257,6 +374,71 @@ where
         scope.create();
         scope
     }
+
+    /// Creates a component which isn't attached to the DOM. Updates of the
+    /// component render it immediately and the frame is kept in the scope.
+    pub(crate) fn mount_headless(self, props: COMP::Properties) -> Scope<COMP> {
+        let create = CreateHeadless {
+            scope: self.clone(),
+            props,
+        };
+        self.scheduler.put_and_try_run(Box::new(create));
+        self
+    }
+
+    /// Returns the component created by `mount_headless`.
+    pub(crate) fn headless_component(&self) -> Ref<'_, COMP> {
+        Ref::map(self.shared_state.borrow(), |state| match state {
+            ComponentState::Headless(this) => &this.component,
+            state => panic!("unexpected component state: {}", state),
+        })
+    }
+
+    /// Returns the last frame rendered by a component created by `mount_headless`.
+    pub(crate) fn headless_frame(&self) -> Ref<'_, VNode<COMP>> {
+        Ref::map(self.shared_state.borrow(), |state| match state {
+            ComponentState::Headless(this) => &this.last_frame,
+            state => panic!("unexpected component state: {}", state),
+        })
+    }
+
+    /// Creates a component which isn't attached to the DOM and renders
+    /// its view to an HTML string. Messages sent to the component are dropped.
+    pub(crate) fn render_html(self, props: COMP::Properties) -> String {
+        *self.shared_state.borrow_mut() = ComponentState::Destroyed;
+        let link = ComponentLink::connect(&self);
+        let mut component = COMP::create(props, link);
+        let html = component.view().to_html_string();
+        component.destroy();
+        html
+    }
+}
+
+struct CreateHeadless<COMP>
+where
+    COMP: Component,
+{
+    scope: Scope<COMP>,
+    props: COMP::Properties,
+}
+
+impl<COMP> Runnable for CreateHeadless<COMP>
+where
+    COMP: Component + Renderable<COMP>,
+{
+    fn run(self: Box<Self>) {
+        let CreateHeadless { scope, props } = *self;
+        scope.shared_state.replace(ComponentState::Processing);
+        let link = ComponentLink::connect(&scope);
+        let component = COMP::create(props, link);
+        let last_frame = profiler::measure::<COMP, _, _>(Phase::View, || component.view());
+        scope
+            .shared_state
+            .replace(ComponentState::Headless(HeadlessState {
+                component,
+                last_frame,
+            }));
+    }
 }
 
 struct CreateComponent<COMP>
@@ -274,7 +456,9 @@ where
         let current_state = self.shared_state.replace(ComponentState::Processing);
         self.shared_state.replace(match current_state {
             ComponentState::Ready(state) => ComponentState::Created(state.create().update()),
-            ComponentState::Created(_) | ComponentState::Destroyed => current_state,
+            ComponentState::Created(_)
+            | ComponentState::Headless(_)
+            | ComponentState::Destroyed => current_state,
             ComponentState::Empty | ComponentState::Processing => {
                 panic!("unexpected component state: {}", current_state);
             }
@@ -296,16 +480,19 @@ where
     fn run(self: Box<Self>) {
         match self.shared_state.replace(ComponentState::Destroyed) {
             ComponentState::Created(mut this) => {
-                this.component.destroy();
                 if let Some(last_frame) = &mut this.last_frame {
                     last_frame.detach(this.element.as_node());
                 }
+                this.component.destroy();
             }
             ComponentState::Ready(mut this) => {
                 if let Some(ancestor) = &mut this.ancestor {
                     ancestor.detach(this.element.as_node());
                 }
             }
+            ComponentState::Headless(mut this) => {
+                this.component.destroy();
+            }
             ComponentState::Empty | ComponentState::Destroyed => {}
             s @ ComponentState::Processing => panic!("unexpected component state: {}", s),
         };
@@ -328,14 +515,53 @@ where
         let current_state = self.shared_state.replace(ComponentState::Processing);
         self.shared_state.replace(match current_state {
             ComponentState::Created(mut this) => {
-                let should_update = match self.update {
-                    ComponentUpdate::Message(msg) => this.component.update(msg),
-                    ComponentUpdate::Properties(props) => this.component.change(props),
-                };
-                let next_state = if should_update { this.update() } else { this };
-                ComponentState::Created(next_state)
+                let should_update = apply_update(&mut this.component, self.update);
+                // Rendering is deferred to render once for all updates
+                // which were sent before the next frame.
+                if should_update && !this.render_scheduled {
+                    this.render_scheduled = true;
+                    let render = RenderComponent {
+                        shared_state: self.shared_state.clone(),
+                    };
+                    this.env.scheduler.put_render(Box::new(render));
+                }
+                ComponentState::Created(this)
+            }
+            ComponentState::Headless(mut this) => {
+                if apply_update(&mut this.component, self.update) {
+                    let component = &this.component;
+                    this.last_frame =
+                        profiler::measure::<COMP, _, _>(Phase::View, || component.view());
+                }
+                ComponentState::Headless(this)
+            }
+            ComponentState::Destroyed => {
+                diagnostics::message_to_destroyed::<COMP>();
+                current_state
             }
-            ComponentState::Destroyed => current_state,
+            ComponentState::Processing | ComponentState::Ready(_) | ComponentState::Empty => {
+                panic!("unexpected component state: {}", current_state);
+            }
+        });
+    }
+}
+
+struct RenderComponent<COMP>
+where
+    COMP: Component,
+{
+    shared_state: Shared<ComponentState<COMP>>,
+}
+
+impl<COMP> Runnable for RenderComponent<COMP>
+where
+    COMP: Component + Renderable<COMP>,
+{
+    fn run(self: Box<Self>) {
+        let current_state = self.shared_state.replace(ComponentState::Processing);
+        self.shared_state.replace(match current_state {
+            ComponentState::Created(this) => ComponentState::Created(this.update()),
+            ComponentState::Headless(_) | ComponentState::Destroyed => current_state,
             ComponentState::Processing | ComponentState::Ready(_) | ComponentState::Empty => {
                 panic!("unexpected component state: {}", current_state);
             }
@@ -346,12 +572,120 @@ where
 /// A type which expected as a result of `view` function implementation.
 pub type Html<MSG> = VNode<MSG>;
 
+/// A list of children which a component gets from `html!`:
+///
+/// ```rust,ignore
+/// html! {
+///     <Panel title="Info">
+///         <p>{ "Children are rendered by the panel" }</p>
+///     </Panel>
+/// }
+/// ```
+///
+/// Children are built again every time they are rendered, so they
+/// can't borrow from the parent component and have to own their data.
+pub struct ChildrenRenderer<T> {
+    len: usize,
+    render: Rc<dyn Fn() -> Vec<T>>,
+}
+
+/// Children of a component. Put it to the `children` field of properties.
+pub type Children<COMP> = ChildrenRenderer<Html<COMP>>;
+
+/// Children which are components of the `CHILD` type only.
+/// The properties of every child could be read or changed before rendering.
+pub type ChildrenWithProps<CHILD, COMP> = ChildrenRenderer<VChild<CHILD, COMP>>;
+
+impl<T> ChildrenRenderer<T> {
+    /// Creates children with a function which builds them.
+    pub fn new<F>(len: usize, render: F) -> Self
+    where
+        F: Fn() -> Vec<T> + 'static,
+    {
+        ChildrenRenderer {
+            len,
+            render: Rc::new(render),
+        }
+    }
+
+    /// Returns the number of children.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if there are no children.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Builds children to iterate or to wrap every child.
+    pub fn iter(&self) -> impl Iterator<Item = T> {
+        (self.render)().into_iter()
+    }
+}
+
+impl<COMP: Component> ChildrenRenderer<Html<COMP>> {
+    /// Renders all children in place.
+    pub fn render(&self) -> Html<COMP> {
+        let mut vlist = VList::new();
+        for child in self.iter() {
+            vlist.add_child(child);
+        }
+        vlist.into()
+    }
+}
+
+impl<CHILD, COMP> ChildrenRenderer<VChild<CHILD, COMP>>
+where
+    CHILD: Component + Renderable<CHILD>,
+    CHILD::Properties: Clone + PartialEq + 'static,
+    COMP: Component,
+{
+    /// Renders all children in place.
+    pub fn render(&self) -> Html<COMP> {
+        let mut vlist = VList::new();
+        for child in self.iter() {
+            vlist.add_child(child.into());
+        }
+        vlist.into()
+    }
+}
+
+impl<T: 'static> Default for ChildrenRenderer<T> {
+    fn default() -> Self {
+        ChildrenRenderer::new(0, Vec::new)
+    }
+}
+
+impl<T> Clone for ChildrenRenderer<T> {
+    fn clone(&self) -> Self {
+        ChildrenRenderer {
+            len: self.len,
+            render: self.render.clone(),
+        }
+    }
+}
+
+impl<T> PartialEq for ChildrenRenderer<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.render, &other.render)
+    }
+}
+
+impl<T> fmt::Debug for ChildrenRenderer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ChildrenRenderer {{ len: {} }}", self.len)
+    }
+}
+
 macro_rules! impl_action {
     ($($action:ident($event:ident : $type:ident) -> $ret:ty => $convert:expr)*) => {$(
         /// An abstract implementation of a listener.
         pub mod $action {
-            use stdweb::web::{IEventTarget, Element};
-            use stdweb::web::event::{IEvent, $type};
+            use stdweb::web::Element;
+            use stdweb::web::event::$type;
+            use stdweb::unstable::TryInto;
+            use stdweb::Value;
             use super::*;
 
             /// A wrapper for a callback.
@@ -361,6 +695,10 @@ macro_rules! impl_action {
             /// And event type which keeps the returned type.
             pub type Event = $ret;
 
+            /// Used by `html!` to report a wrong type of the handler argument.
+            #[doc(hidden)]
+            pub fn expect_event(_: ::std::marker::PhantomData<Event>) {}
+
             impl<F, MSG> From<F> for Wrapper<F>
             where
                 MSG: 'static,
@@ -380,18 +718,30 @@ macro_rules! impl_action {
                     stringify!($action)
                 }
 
-                fn attach(&mut self, element: &Element, mut activator: Scope<COMP>)
-                    -> EventListenerHandle {
+                fn attach(&mut self, element: &Element, activator: Scope<COMP>)
+                    -> ListenerHandle {
                     let handler = self.0.take().expect("tried to attach listener twice");
+                    let delegation = activator.delegation.clone();
+                    crate::diagnostics::detached_listener::<COMP>(
+                        element,
+                        delegation.root().as_ref(),
+                        stringify!($action),
+                    );
                     let this = element.clone();
                     let listener = move |event: $type| {
                         debug!("Event handler: {}", stringify!($type));
-                        event.stop_propagation();
                         let handy_event: $ret = $convert(&this, event);
                         let msg = handler(handy_event);
-                        activator.send_message(msg);
+                        activator.clone().send_message(msg);
                     };
-                    element.add_event_listener(listener)
+                    delegation.attach(element, listener)
+                }
+
+                fn handle(&self, element: &Element, event: Value) -> Option<COMP::Message> {
+                    let handler = self.0.as_ref()?;
+                    let event: $type = event.try_into().ok()?;
+                    let handy_event: $ret = $convert(element, event);
+                    Some(handler(handy_event))
                 }
             }
         }
@@ -546,3 +896,130 @@ impl ToString for Href {
         self.link.to_owned()
     }
 }
+
+/// Wrapped `Node` reference for later use in component lifecycle methods.
+/// Set it to an element with the `ref` attribute of `html!`:
+///
+/// ```rust,ignore
+/// html! { <div ref=self.node_ref.clone()></div> }
+/// ```
+#[derive(Default, Clone)]
+pub struct NodeRef(Rc<RefCell<NodeRefInner>>);
+
+#[derive(Default)]
+struct NodeRefInner {
+    node: Option<Node>,
+    // Called once when a node is set
+    waiters: Vec<Box<dyn FnOnce(&Node)>>,
+    // Called every time the node changes
+    listeners: Vec<(usize, Rc<dyn Fn(Option<&Node>)>)>,
+    next_listener: usize,
+}
+
+impl PartialEq for NodeRef {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for NodeRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "NodeRef {{ references: {:?} }}", self.get().is_some())
+    }
+}
+
+impl NodeRef {
+    /// Returns the referenced node if it's rendered.
+    pub fn get(&self) -> Option<Node> {
+        self.0.borrow().node.clone()
+    }
+
+    /// Returns the referenced node converted to a specific type
+    /// (e.g. `Element` or `InputElement`).
+    pub fn cast<T: TryFrom<Node>>(&self) -> Option<T> {
+        self.get().and_then(|node| T::try_from(node).ok())
+    }
+
+    /// Calls `waiter` with the referenced node when it's rendered,
+    /// or immediately if it's rendered already.
+    pub(crate) fn when_set<F>(&self, waiter: F)
+    where
+        F: FnOnce(&Node) + 'static,
+    {
+        match self.get() {
+            Some(node) => waiter(&node),
+            None => self.0.borrow_mut().waiters.push(Box::new(waiter)),
+        }
+    }
+
+    /// Calls `listener` with the referenced node if it's rendered and then
+    /// every time the node is replaced or removed, until the returned
+    /// subscription is dropped.
+    pub(crate) fn listen<F>(&self, listener: F) -> NodeRefListener
+    where
+        F: Fn(Option<&Node>) + 'static,
+    {
+        let listener: Rc<dyn Fn(Option<&Node>)> = Rc::new(listener);
+        let id = {
+            let mut inner = self.0.borrow_mut();
+            let id = inner.next_listener;
+            inner.next_listener += 1;
+            inner.listeners.push((id, listener.clone()));
+            id
+        };
+        if let Some(node) = self.get() {
+            listener(Some(&node));
+        }
+        NodeRefListener {
+            node_ref: Rc::downgrade(&self.0),
+            id,
+        }
+    }
+
+    pub(crate) fn set(&self, node: Option<Node>) {
+        let (waiters, listeners) = {
+            let mut inner = self.0.borrow_mut();
+            if inner.node == node {
+                return;
+            }
+            inner.node = node.clone();
+            let waiters = if node.is_some() {
+                inner.waiters.drain(..).collect()
+            } else {
+                Vec::new()
+            };
+            let listeners: Vec<_> = inner.listeners.iter().map(|(_, f)| f.clone()).collect();
+            (waiters, listeners)
+        };
+        if let Some(ref node) = node {
+            for waiter in waiters {
+                waiter(node);
+            }
+        }
+        for listener in listeners {
+            listener(node.as_ref());
+        }
+    }
+}
+
+/// A subscription to changes of a `NodeRef` created by `NodeRef::listen`.
+/// The listener is removed when it's dropped.
+pub(crate) struct NodeRefListener {
+    node_ref: Weak<RefCell<NodeRefInner>>,
+    id: usize,
+}
+
+impl Drop for NodeRefListener {
+    fn drop(&mut self) {
+        if let Some(inner) = self.node_ref.upgrade() {
+            let listener = {
+                let mut inner = inner.borrow_mut();
+                let index = inner.listeners.iter().position(|(id, _)| *id == self.id);
+                index.map(|index| inner.listeners.remove(index))
+            };
+            // The listener is dropped after the borrow is released,
+            // because it could own other references.
+            drop(listener);
+        }
+    }
+}
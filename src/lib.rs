@@ -73,18 +73,26 @@ pub use yew_macro::html;
 
 /// This module contains macros which implements html! macro and JSX-like templates
 pub mod macros {
+    pub use crate::bind;
+    pub use crate::css;
     pub use crate::html;
-    pub use yew_macro::Properties;
+    pub use yew_macro::{Properties, Routable};
 }
 
 pub mod agent;
 pub mod app;
 pub mod callback;
 pub mod components;
+pub mod diagnostics;
+pub mod form;
 pub mod format;
 pub mod html;
+pub mod router;
 pub mod scheduler;
 pub mod services;
+pub mod store;
+pub mod style;
+pub mod test;
 pub mod utils;
 pub mod virtual_dom;
 
@@ -134,19 +142,21 @@ where
 /// ```
 pub mod prelude {
     pub use crate::agent::{Bridge, Bridged, Threaded};
-    pub use crate::app::App;
+    pub use crate::app::{App, AppHandle};
     pub use crate::callback::Callback;
     pub use crate::events::*;
+    pub use crate::form::{Binding, Change, FormState};
     pub use crate::html::{
-        Component, ComponentLink, Href, Html, Properties, Renderable, ShouldRender,
+        Children, ChildrenWithProps, Component, ComponentLink, Href, Html, NodeRef, Properties,
+        Renderable, ShouldRender,
     };
     pub use crate::macros::*;
 
     /// Prelude module for creating worker.
     pub mod worker {
         pub use crate::agent::{
-            Agent, AgentLink, Bridge, Bridged, Context, Global, HandlerId, Job, Private, Public,
-            Transferable,
+            Agent, AgentLink, Bridge, Bridged, Context, Global, HandlerId, Job, Pool, Private,
+            Public, Request, RequestBridge, RequestId, Response, Transferable,
         };
     }
 }
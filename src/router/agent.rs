@@ -0,0 +1,113 @@
+//! This module contains an agent which lets any component navigate
+//! and subscribe to route changes without passing callbacks around.
+
+use super::{Routable, RouteService};
+use crate::agent::{Agent, AgentLink, Context, HandlerId, Transferable};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+#[allow(unused_imports)]
+use stdweb::{_js_impl, js};
+
+/// A request of navigation to `RouteAgent`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum RouteRequest {
+    /// Sets the path and creates a new entry in the history.
+    Push(String),
+    /// Sets the path and replaces the current entry in the history.
+    Replace(String),
+    /// Goes to the previous entry in the history.
+    Back,
+    /// Goes to the next entry in the history.
+    Forward,
+    /// Asks for the current path. It's sent back to the requester only.
+    GetCurrentPath,
+}
+
+impl RouteRequest {
+    /// Creates a request to push the path of the route.
+    pub fn push<R: Routable>(route: &R) -> Self {
+        RouteRequest::Push(route.to_path())
+    }
+
+    /// Creates a request to replace the current entry with the path of the route.
+    pub fn replace<R: Routable>(route: &R) -> Self {
+        RouteRequest::Replace(route.to_path())
+    }
+}
+
+impl Transferable for RouteRequest {}
+
+/// A path of the route which the agent sends to subscribers.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RoutePath(pub String);
+
+impl RoutePath {
+    /// Matches the path with routes of the type.
+    pub fn route<R: Routable>(&self) -> Option<R> {
+        R::from_path(&self.0)
+    }
+}
+
+impl Transferable for RoutePath {}
+
+/// Internal message of the agent.
+pub enum Msg {
+    /// The path was changed by navigation.
+    RouteChanged(String),
+}
+
+/// An agent which keeps the route. Every connected bridge gets
+/// the path when the route changes.
+pub struct RouteAgent {
+    link: AgentLink<RouteAgent>,
+    service: RouteService,
+    subscribers: HashSet<HandlerId>,
+}
+
+impl Agent for RouteAgent {
+    type Reach = Context;
+    type Message = Msg;
+    type Input = RouteRequest;
+    type Output = RoutePath;
+
+    fn create(link: AgentLink<Self>) -> Self {
+        let mut service = RouteService::new();
+        service.register_callback(link.send_back(Msg::RouteChanged));
+        RouteAgent {
+            link,
+            service,
+            subscribers: HashSet::new(),
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) {
+        match msg {
+            Msg::RouteChanged(path) => {
+                for sub in &self.subscribers {
+                    self.link.response(*sub, RoutePath(path.clone()));
+                }
+            }
+        }
+    }
+
+    fn connected(&mut self, id: HandlerId) {
+        self.subscribers.insert(id);
+    }
+
+    fn handle(&mut self, msg: Self::Input, id: HandlerId) {
+        match msg {
+            RouteRequest::Push(path) => self.service.set_route(&path),
+            RouteRequest::Replace(path) => self.service.replace_route(&path),
+            RouteRequest::Back => self.service.back(),
+            RouteRequest::Forward => self.service.forward(),
+            RouteRequest::GetCurrentPath => {
+                let path = self.service.get_path();
+                self.link.response(id, RoutePath(path));
+            }
+        }
+    }
+
+    fn disconnected(&mut self, id: HandlerId) {
+        self.subscribers.remove(&id);
+    }
+}
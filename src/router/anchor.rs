@@ -0,0 +1,88 @@
+//! This module contains implementation of `RouterAnchor` component.
+//! It renders a link to the route and navigates without reloading the page:
+//!
+//! ```
+//! html! {
+//!     <RouterAnchor<AppRoute> route=AppRoute::User { id: 42 } text="Profile" />
+//! }
+//! ```
+
+use super::{Routable, RouteService};
+use crate::html::{Component, ComponentLink, Html, Renderable, ShouldRender};
+use crate::macros::{html, Properties};
+use stdweb::web::event::IEvent;
+
+/// `RouterAnchor` component.
+pub struct RouterAnchor<R: Routable> {
+    props: Props<R>,
+    service: RouteService,
+}
+
+/// Internal message of the component.
+pub enum Msg {
+    /// The anchor was clicked.
+    Clicked,
+}
+
+/// Properties of `RouterAnchor` component.
+#[derive(Clone, Properties)]
+pub struct Props<R: Routable> {
+    /// The route to navigate to.
+    #[props(required)]
+    pub route: R,
+    /// Text of the anchor.
+    pub text: String,
+    /// Classes of the anchor.
+    pub classes: String,
+}
+
+// Routes are compared by their paths, they don't have to implement `PartialEq`
+impl<R: Routable> PartialEq for Props<R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.route.to_path() == other.route.to_path()
+            && self.text == other.text
+            && self.classes == other.classes
+    }
+}
+
+impl<R: Routable> Component for RouterAnchor<R> {
+    type Message = Msg;
+    type Properties = Props<R>;
+
+    fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+        RouterAnchor {
+            props,
+            service: RouteService::new(),
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Clicked => {
+                let path = self.props.route.to_path();
+                self.service.set_route(&path);
+            }
+        }
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props = props;
+        true
+    }
+}
+
+impl<R: Routable> Renderable<RouterAnchor<R>> for RouterAnchor<R> {
+    fn view(&self) -> Html<Self> {
+        html! {
+            <a href=self.props.route.to_path()
+               class=self.props.classes.as_str()
+               onclick=|event| {
+                   event.prevent_default();
+                   Msg::Clicked
+               }>
+                { &self.props.text }
+            </a>
+        }
+    }
+}
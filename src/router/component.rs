@@ -0,0 +1,115 @@
+//! This module contains implementation of `Router` component.
+//! It renders a view for the route matched by the current path. Example:
+//!
+//! ```
+//! fn view(&self) -> Html<Self> {
+//!     html! {
+//!         <Router<AppRoute> render=Render::from(|route| match route {
+//!             Some(AppRoute::Home) => html! { <Home /> },
+//!             Some(AppRoute::User { id }) => html! { <User id=id /> },
+//!             None => html! { <NotFound /> },
+//!         }) />
+//!     }
+//! }
+//! ```
+
+use super::{scroll, Routable, RouteService};
+use crate::html::{Component, ComponentLink, Html, Renderable, ShouldRender};
+use crate::macros::Properties;
+use std::rc::Rc;
+
+/// A function which renders a view for the route.
+/// It gets `None` if the path doesn't match any route.
+pub struct Render<R: Routable>(Rc<dyn Fn(Option<R>) -> Html<Router<R>>>);
+
+impl<R, F> From<F> for Render<R>
+where
+    R: Routable,
+    F: Fn(Option<R>) -> Html<Router<R>> + 'static,
+{
+    fn from(func: F) -> Self {
+        Render(Rc::new(func))
+    }
+}
+
+impl<R: Routable> Clone for Render<R> {
+    fn clone(&self) -> Self {
+        Render(self.0.clone())
+    }
+}
+
+impl<R: Routable> PartialEq for Render<R> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// `Router` component.
+pub struct Router<R: Routable> {
+    route: Option<R>,
+    render: Render<R>,
+    // Keeps the listener of route changes alive
+    _service: RouteService,
+}
+
+/// Internal message of the component.
+pub enum Msg {
+    /// The path was changed by navigation.
+    RouteChanged(String),
+}
+
+/// Properties of `Router` component.
+#[derive(Clone, Properties)]
+pub struct Props<R: Routable> {
+    /// Renders a view for the current route.
+    #[props(required)]
+    pub render: Render<R>,
+}
+
+impl<R: Routable> PartialEq for Props<R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.render == other.render
+    }
+}
+
+impl<R: Routable> Component for Router<R> {
+    type Message = Msg;
+    type Properties = Props<R>;
+
+    fn create(props: Self::Properties, mut link: ComponentLink<Self>) -> Self {
+        scroll::init();
+        let mut service = RouteService::new();
+        service.register_callback(link.send_back(Msg::RouteChanged));
+        Router {
+            route: R::from_path(&service.get_path()),
+            render: props.render,
+            _service: service,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::RouteChanged(path) => {
+                self.route = R::from_path(&path);
+                let restore = self.route.as_ref().map_or(true, R::restore_scroll);
+                if restore {
+                    scroll::restore();
+                } else {
+                    scroll::skip();
+                }
+            }
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.render = props.render;
+        true
+    }
+}
+
+impl<R: Routable> Renderable<Router<R>> for Router<R> {
+    fn view(&self) -> Html<Self> {
+        (self.render.0)(self.route.clone())
+    }
+}
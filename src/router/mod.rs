@@ -0,0 +1,50 @@
+//! This module contains a typed router. Routes are described with an enum
+//! which implements `Routable` trait, usually with the derive macro:
+//!
+//! ```
+//! use yew::Routable;
+//!
+//! #[derive(Clone, Routable)]
+//! enum AppRoute {
+//!     #[at("/")]
+//!     Home,
+//!     #[at("/users/{id}")]
+//!     User { id: u32 },
+//! }
+//! ```
+//!
+//! `Router` component renders a view for the current route,
+//! `RouterAnchor` navigates to a route, `RouteAgent` lets any component
+//! navigate and subscribe to route changes and `RouteService` gives
+//! access to the History API.
+//!
+//! `Router` restores the scroll position of a page when a user comes
+//! back to it with the 'back' or 'forward' buttons. A route opts out
+//! with `#[at("/chat", restore_scroll = false)]`.
+
+pub mod agent;
+pub mod anchor;
+pub mod component;
+mod scroll;
+pub mod service;
+
+pub use self::agent::{RouteAgent, RoutePath, RouteRequest};
+pub use self::anchor::RouterAnchor;
+pub use self::component::{Render, Router};
+pub use self::service::RouteService;
+
+/// Routes which could be created from a path and converted back to it.
+pub trait Routable: Clone + Sized + 'static {
+    /// Matches the path and extracts captured values.
+    /// Returns `None` if the path doesn't match any route.
+    fn from_path(path: &str) -> Option<Self>;
+
+    /// Builds the path of the route.
+    fn to_path(&self) -> String;
+
+    /// Returns `false` if `Router` shouldn't restore the scroll position
+    /// of the route on back and forward navigation.
+    fn restore_scroll(&self) -> bool {
+        true
+    }
+}
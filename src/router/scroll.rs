@@ -0,0 +1,160 @@
+//! This module keeps scroll positions of entries of the history.
+//! Every entry created by `RouteService` gets a key in its state, the
+//! position is saved when the entry is left and `Router` restores it
+//! when a user comes back with the 'back' or 'forward' buttons.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use stdweb::unstable::TryInto;
+use stdweb::web::Date;
+use stdweb::Value;
+#[allow(unused_imports)]
+use stdweb::{_js_impl, js};
+
+#[derive(Default)]
+struct Entries {
+    session: Option<u64>,
+    next_key: u64,
+    current: Option<String>,
+    positions: HashMap<String, (f64, f64)>,
+    pending: bool,
+}
+
+impl Entries {
+    fn new_key(&mut self) -> String {
+        // Keys of previous page loads stay in the history,
+        // so they are prefixed with the time of the load
+        let session = *self.session.get_or_insert_with(|| Date::now() as u64);
+        self.next_key += 1;
+        format!("{}-{}", session, self.next_key)
+    }
+
+    fn save_current(&mut self) {
+        if let Some(key) = self.current.clone() {
+            let position: Vec<f64> = js! {
+                return [window.pageXOffset, window.pageYOffset];
+            }
+            .try_into()
+            .unwrap_or_default();
+            if let [x, y] = position[..] {
+                self.positions.insert(key, (x, y));
+            }
+        }
+    }
+}
+
+thread_local! {
+    static ENTRIES: RefCell<Entries> = RefCell::new(Entries::default());
+}
+
+/// Takes over scroll restoration from the browser and marks the current entry.
+pub(crate) fn init() {
+    ENTRIES.with(|entries| {
+        let mut entries = entries.borrow_mut();
+        if entries.current.is_some() {
+            return;
+        }
+        let state: Value = js! { return window.history.state; };
+        let key = match entry_key(&state) {
+            Some(key) => key,
+            None => {
+                let key = entries.new_key();
+                js! { @(no_return)
+                    window.history.replaceState({ yew_entry: @{&key} }, "");
+                }
+                key
+            }
+        };
+        js! { @(no_return)
+            if ("scrollRestoration" in window.history) {
+                window.history.scrollRestoration = "manual";
+            }
+        }
+        entries.current = Some(key);
+    });
+}
+
+/// Saves the position of the current entry and pushes a new one.
+pub(crate) fn push_state(path: &str) {
+    ENTRIES.with(|entries| {
+        let mut entries = entries.borrow_mut();
+        entries.save_current();
+        let key = entries.new_key();
+        js! { @(no_return)
+            window.history.pushState({ yew_entry: @{&key} }, "", @{path});
+        }
+        entries.current = Some(key);
+    });
+}
+
+/// Replaces the path of the current entry and keeps its key.
+pub(crate) fn replace_state(path: &str) {
+    ENTRIES.with(|entries| {
+        let mut entries = entries.borrow_mut();
+        let key = match entries.current.clone() {
+            Some(key) => key,
+            None => entries.new_key(),
+        };
+        js! { @(no_return)
+            window.history.replaceState({ yew_entry: @{&key} }, "", @{path});
+        }
+        entries.current = Some(key);
+    });
+}
+
+/// Tracks an entry which a user navigated to with `popstate` event.
+/// Every `RouteService` calls it, but the entry changes once.
+pub(crate) fn entered(state: &Value) {
+    let key = match entry_key(state) {
+        Some(key) => key,
+        // Events of `pushState` and `replaceState` have no state
+        None => return,
+    };
+    ENTRIES.with(|entries| {
+        let mut entries = entries.borrow_mut();
+        if entries.current.as_ref() == Some(&key) {
+            return;
+        }
+        entries.save_current();
+        entries.pending = entries.positions.contains_key(&key);
+        entries.current = Some(key);
+    });
+}
+
+/// Scrolls to the saved position of the entry a user came back to
+/// after the next render. Does nothing for new entries.
+pub(crate) fn restore() {
+    let position = ENTRIES.with(|entries| {
+        let mut entries = entries.borrow_mut();
+        if !entries.pending {
+            return None;
+        }
+        entries.pending = false;
+        let current = entries.current.as_ref()?;
+        entries.positions.get(current).cloned()
+    });
+    if let Some((x, y)) = position {
+        js! { @(no_return)
+            var x = @{x};
+            var y = @{y};
+            requestAnimationFrame(function() {
+                setTimeout(function() {
+                    window.scrollTo(x, y);
+                }, 0);
+            });
+        }
+    }
+}
+
+/// Forgets a position which should be restored, the route opted out.
+pub(crate) fn skip() {
+    ENTRIES.with(|entries| entries.borrow_mut().pending = false);
+}
+
+fn entry_key(state: &Value) -> Option<String> {
+    let key: Value = js! {
+        var state = @{state};
+        return state && state.yew_entry || null;
+    };
+    key.try_into().ok()
+}
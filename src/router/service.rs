@@ -0,0 +1,114 @@
+//! This module contains the implementation of a service to
+//! interact with the History API of a browser.
+
+use super::scroll;
+use crate::callback::Callback;
+use stdweb::web::event::PopStateEvent;
+use stdweb::web::{window, EventListenerHandle, IEventTarget, Location};
+#[allow(unused_imports)]
+use stdweb::{_js_impl, js};
+
+/// A service to change the path in the URL bar and to track
+/// navigation by the 'back' and 'forward' buttons.
+pub struct RouteService {
+    location: Location,
+    listener: Option<EventListenerHandle>,
+}
+
+impl RouteService {
+    /// Creates a new service instance.
+    pub fn new() -> Self {
+        let location = window()
+            .location()
+            .expect("browser does not support location API");
+        RouteService {
+            location,
+            listener: None,
+        }
+    }
+
+    /// Registers a callback which gets the path every time when the route changes.
+    /// It replaces a previously registered callback.
+    pub fn register_callback(&mut self, callback: Callback<String>) {
+        if let Some(listener) = self.listener.take() {
+            listener.remove();
+        }
+        let location = self.location.clone();
+        let listener = window().add_event_listener(move |event: PopStateEvent| {
+            scroll::entered(&event.state());
+            let path = location.pathname().expect("can't get path of the location");
+            callback.emit(path);
+        });
+        self.listener = Some(listener);
+    }
+
+    /// Sets the route and creates a new entry in the history.
+    /// The route should be a relative path that starts with a '/'.
+    pub fn set_route(&mut self, route: &str) {
+        scroll::push_state(route);
+        notify_route_changed();
+    }
+
+    /// Sets the route, but replaces the current entry in the history.
+    pub fn replace_route(&mut self, route: &str) {
+        scroll::replace_state(route);
+        notify_route_changed();
+    }
+
+    /// Goes to the previous entry in the history.
+    pub fn back(&mut self) {
+        js! { @(no_return)
+            window.history.back();
+        }
+    }
+
+    /// Goes to the next entry in the history.
+    pub fn forward(&mut self) {
+        js! { @(no_return)
+            window.history.forward();
+        }
+    }
+
+    /// Gets the path of the current url.
+    pub fn get_path(&self) -> String {
+        self.location
+            .pathname()
+            .expect("can't get path of the location")
+    }
+
+    /// Gets the query string of the current url.
+    pub fn get_query(&self) -> String {
+        self.location
+            .search()
+            .expect("can't get query of the location")
+    }
+
+    /// Gets the fragment of the current url.
+    pub fn get_fragment(&self) -> String {
+        self.location
+            .hash()
+            .expect("can't get fragment of the location")
+    }
+}
+
+impl Default for RouteService {
+    fn default() -> Self {
+        RouteService::new()
+    }
+}
+
+impl Drop for RouteService {
+    fn drop(&mut self) {
+        if let Some(listener) = self.listener.take() {
+            listener.remove();
+        }
+    }
+}
+
+/// History API doesn't emit `popstate` for `pushState` and `replaceState`
+/// calls, so it's emitted manually to notify every registered callback.
+fn notify_route_changed() {
+    js! { @(no_return)
+        window.dispatchEvent(new PopStateEvent("popstate", { state: null }));
+    }
+}
@@ -1,31 +1,66 @@
 //! This module contains a scheduler.
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
+#[cfg(feature = "futures")]
+use std::future::Future;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, Ordering};
+use stdweb::web::window;
 
 pub(crate) type Shared<T> = Rc<RefCell<T>>;
 
 thread_local! {
     static SCHEDULER: Rc<Scheduler> =
         Rc::new(Scheduler::new());
+    static RENDER_MODE: Cell<RenderMode> = Cell::new(RenderMode::AnimationFrame);
 }
 
 pub(crate) fn scheduler() -> Rc<Scheduler> {
     SCHEDULER.with(Rc::clone)
 }
 
+/// Spawns a future on the event loop of the environment.
+#[cfg(feature = "futures")]
+pub(crate) fn spawn_local<F>(future: F)
+where
+    F: Future<Output = ()> + 'static,
+{
+    #[cfg(all(target_arch = "wasm32", not(cargo_web)))]
+    wasm_bindgen_futures::spawn_local(future);
+    #[cfg(not(all(target_arch = "wasm32", not(cargo_web))))]
+    stdweb::spawn_local(future);
+}
+
 /// A routine which could be run.
 pub(crate) trait Runnable {
     /// Runs a routine with a context instance.
     fn run(self: Box<Self>);
 }
 
-/// This is a global scheduler suitable to schedule and run any tasks.
+/// Defines when the DOM is patched after updates of components.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenderMode {
+    /// Renders are coalesced and flushed on the next animation frame.
+    AnimationFrame,
+    /// Components render right after the update. Useful for tests.
+    Immediate,
+}
+
+/// Sets the render mode of schedulers of all apps of the current thread.
+/// `RenderMode::AnimationFrame` is used by default.
+pub fn set_render_mode(mode: RenderMode) {
+    RENDER_MODE.with(|render_mode| render_mode.set(mode));
+}
+
+/// A scheduler suitable to schedule and run any tasks. Every app has its
+/// own scheduler and agents use the global one of the thread.
 pub(crate) struct Scheduler {
     lock: Rc<AtomicBool>,
     sequence: Shared<VecDeque<Box<dyn Runnable>>>,
+    renders: Shared<Vec<Box<dyn Runnable>>>,
+    frame_requested: Rc<AtomicBool>,
+    flushing: Rc<AtomicBool>,
 }
 
 impl Clone for Scheduler {
@@ -33,18 +68,55 @@ impl Clone for Scheduler {
         Scheduler {
             lock: self.lock.clone(),
             sequence: self.sequence.clone(),
+            renders: self.renders.clone(),
+            frame_requested: self.frame_requested.clone(),
+            flushing: self.flushing.clone(),
         }
     }
 }
 
 impl Scheduler {
     /// Creates a new scheduler with a context.
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         let sequence = VecDeque::new();
         Scheduler {
             lock: Rc::new(AtomicBool::new(false)),
             sequence: Rc::new(RefCell::new(sequence)),
+            renders: Rc::new(RefCell::new(Vec::new())),
+            frame_requested: Rc::new(AtomicBool::new(false)),
+            flushing: Rc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Puts a render routine which will be run on the next animation frame.
+    /// Renders requested while flushing are run immediately to let
+    /// child components render in the same frame.
+    pub(crate) fn put_render(&self, runnable: Box<dyn Runnable>) {
+        let render_mode = RENDER_MODE.with(Cell::get);
+        if render_mode == RenderMode::Immediate || self.flushing.load(Ordering::Relaxed) {
+            self.put_and_try_run(runnable);
+            return;
+        }
+        self.renders.borrow_mut().push(runnable);
+        if !self.frame_requested.swap(true, Ordering::Relaxed) {
+            let this = self.clone();
+            window().request_animation_frame(move |_| this.flush_renders());
+        }
+    }
+
+    /// Drops renders which weren't flushed yet.
+    pub(crate) fn clear_renders(&self) {
+        self.renders.borrow_mut().clear();
+    }
+
+    fn flush_renders(&self) {
+        self.frame_requested.store(false, Ordering::Relaxed);
+        self.flushing.store(true, Ordering::Relaxed);
+        let renders = self.renders.replace(Vec::new());
+        for runnable in renders {
+            self.put_and_try_run(runnable);
         }
+        self.flushing.store(false, Ordering::Relaxed);
     }
 
     pub(crate) fn put_and_try_run(&self, runnable: Box<dyn Runnable>) {
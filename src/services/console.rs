@@ -1,8 +1,37 @@
 //! This module contains a service implementation to use browser's console.
-
+//!
+//! Messages are filtered by a level which can be changed at runtime with
+//! `ConsoleService::set_level`. The module also contains a backend of
+//! the `log` crate which prints records to the console, it's installed
+//! by `init_logger`.
+
+use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 #[allow(unused_imports)]
 use stdweb::{_js_impl, js};
 
+static LEVEL: AtomicUsize = AtomicUsize::new(LevelFilter::Trace as usize);
+
+// Set when `ConsoleLogger` is the logger of the `log` crate.
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+fn enabled(level: Level) -> bool {
+    level as usize <= LEVEL.load(Ordering::Relaxed)
+}
+
+fn print(level: Level, message: &str) {
+    if !enabled(level) {
+        return;
+    }
+    match level {
+        Level::Error => js! { @(no_return) console.error(@{message}); },
+        Level::Warn => js! { @(no_return) console.warn(@{message}); },
+        Level::Info => js! { @(no_return) console.info(@{message}); },
+        Level::Debug | Level::Trace => js! { @(no_return) console.debug(@{message}); },
+    }
+}
+
 /// A service to use methods of a
 /// [Console](https://developer.mozilla.org/en-US/docs/Web/API/Console).
 #[derive(Default)]
@@ -17,31 +46,33 @@ impl ConsoleService {
     /// [console.log](https://developer.mozilla.org/en-US/docs/Web/API/Console/log)
     /// method implementation.
     pub fn log(&mut self, message: &str) {
-        js! { @(no_return) console.log(@{message}); }
+        if enabled(Level::Info) {
+            js! { @(no_return) console.log(@{message}); }
+        }
     }
 
     /// [console.warn](https://developer.mozilla.org/en-US/docs/Web/API/Console/warn)
     /// method implementation.
     pub fn warn(&mut self, message: &str) {
-        js! { @(no_return) console.warn(@{message}); }
+        print(Level::Warn, message);
     }
 
     /// [console.info](https://developer.mozilla.org/en-US/docs/Web/API/Console/info)
     /// method implementation.
     pub fn info(&mut self, message: &str) {
-        js! { @(no_return) console.info(@{message}); }
+        print(Level::Info, message);
     }
 
     /// [console.error](https://developer.mozilla.org/en-US/docs/Web/API/Console/error)
     /// method implementation.
     pub fn error(&mut self, message: &str) {
-        js! { @(no_return) console.error(@{message}); }
+        print(Level::Error, message);
     }
 
     /// [console.debug](https://developer.mozilla.org/en-US/docs/Web/API/Console/debug)
     /// method implementation.
     pub fn debug(&mut self, message: &str) {
-        js! { @(no_return) console.debug(@{message}); }
+        print(Level::Debug, message);
     }
 
     /// [console.count_named](https://developer.mozilla.org/en-US/docs/Web/API/Console/count_named)
@@ -91,12 +122,24 @@ impl ConsoleService {
         js! { @(no_return) console.group(); }
     }
 
+    /// [console.group](https://developer.mozilla.org/en-US/docs/Web/API/Console/group)
+    /// method implementation with a label.
+    pub fn group_named(&mut self, label: &str) {
+        js! { @(no_return) console.group(@{label}); }
+    }
+
     /// [console.group_collapsed](https://developer.mozilla.org/en-US/docs/Web/API/Console/group_collapsed)
     /// method implementation.
     pub fn group_collapsed(&mut self) {
         js! { @(no_return) console.groupCollapsed(); }
     }
 
+    /// [console.group_collapsed](https://developer.mozilla.org/en-US/docs/Web/API/Console/group_collapsed)
+    /// method implementation with a label.
+    pub fn group_collapsed_named(&mut self, label: &str) {
+        js! { @(no_return) console.groupCollapsed(@{label}); }
+    }
+
     /// [console.group_end](https://developer.mozilla.org/en-US/docs/Web/API/Console/group_end)
     /// method implementation.
     pub fn group_end(&mut self) {
@@ -114,4 +157,65 @@ impl ConsoleService {
     pub fn assert(&mut self, condition: bool, message: &str) {
         js! { @(no_return) console.assert(@{condition}, @{message}); }
     }
+
+    /// [console.table](https://developer.mozilla.org/en-US/docs/Web/API/Console/table)
+    /// method implementation. Every row is serialized to a JSON object,
+    /// so fields become columns.
+    pub fn table<T: Serialize>(&mut self, rows: &[T]) {
+        match serde_json::to_string(rows) {
+            Ok(json) => js! { @(no_return) console.table(JSON.parse(@{json})); },
+            Err(err) => self.error(&format!("can't serialize a table: {}", err)),
+        }
+    }
+
+    /// Returns the level of messages which are printed.
+    pub fn level(&self) -> LevelFilter {
+        match LEVEL.load(Ordering::Relaxed) {
+            0 => LevelFilter::Off,
+            1 => LevelFilter::Error,
+            2 => LevelFilter::Warn,
+            3 => LevelFilter::Info,
+            4 => LevelFilter::Debug,
+            _ => LevelFilter::Trace,
+        }
+    }
+
+    /// Skips messages which are less important than `level`, e.g. `LevelFilter::Warn`
+    /// skips `log`, `info` and `debug`. It applies to the `log` backend too if
+    /// it was installed by `init_logger`, levels of other loggers are kept.
+    pub fn set_level(&mut self, level: LevelFilter) {
+        LEVEL.store(level as usize, Ordering::Relaxed);
+        if INSTALLED.load(Ordering::Relaxed) {
+            log::set_max_level(level);
+        }
+    }
+}
+
+struct ConsoleLogger;
+
+static LOGGER: ConsoleLogger = ConsoleLogger;
+
+impl Log for ConsoleLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        enabled(metadata.level())
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if self.enabled(record.metadata()) {
+            let message = format!("{}: {}", record.target(), record.args());
+            print(record.level(), &message);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Routes records of the `log` crate (`log::info!` and others) to the
+/// browser's console and sets the level of `ConsoleService` to `level`.
+/// It fails if another logger was installed before.
+pub fn init_logger(level: LevelFilter) -> Result<(), SetLoggerError> {
+    log::set_logger(&LOGGER)?;
+    INSTALLED.store(true, Ordering::Relaxed);
+    ConsoleService::new().set_level(level);
+    Ok(())
 }
@@ -1,10 +1,33 @@
 //! This module contains the implementation of a service
 //! to show alerts and confirm dialogs in a browser.
+//!
+//! Native dialogs block the event loop, so the service can also open
+//! dialogs rendered by components over the page and report a choice
+//! of a user later through a callback or a future.
 
+use super::Task;
+use crate::callback::Callback;
+use crate::components::confirm::{self, Confirm};
+use crate::html::{Component, Renderable, Scope};
+use std::cell::RefCell;
+#[cfg(feature = "futures")]
+use std::future::Future;
+#[cfg(feature = "futures")]
+use std::pin::Pin;
+use std::rc::Rc;
+#[cfg(feature = "futures")]
+use std::task::{Context, Poll, Waker};
+use stdweb::web::{document, IElement, INode};
 use stdweb::Value;
 #[allow(unused_imports)]
 use stdweb::{_js_impl, js};
 
+type Closer = Rc<RefCell<Option<Box<dyn FnOnce()>>>>;
+
+/// A handle of an open dialog. The dialog is closed when the task is dropped.
+#[must_use]
+pub struct DialogTask(Closer);
+
 /// A dialog service.
 #[derive(Default)]
 pub struct DialogService {}
@@ -30,4 +53,128 @@ impl DialogService {
             _ => false,
         }
     }
+
+    /// Renders a component as a dialog at the end of the document's `<body>`,
+    /// outside of the app's tree. The component reports its result through
+    /// a callback of its properties. The dialog is open until the task is dropped.
+    pub fn open<COMP>(&mut self, props: COMP::Properties) -> DialogTask
+    where
+        COMP: Component + Renderable<COMP>,
+    {
+        let element = document()
+            .create_element("div")
+            .expect("can't create an element for a dialog");
+        element
+            .set_attribute("data-yew-dialog", "")
+            .expect("can't mark an element of a dialog");
+        document()
+            .body()
+            .expect("can't get body node for a dialog")
+            .append_child(&element);
+        let mut scope = Scope::<COMP>::new().mount_app(element.clone(), props);
+        let close = move || {
+            scope.destroy_app();
+            if let Some(parent) = element.parent_node() {
+                let _ = parent.remove_child(&element);
+            }
+        };
+        DialogTask(Rc::new(RefCell::new(Some(Box::new(close)))))
+    }
+
+    /// Opens a `Confirm` dialog with the `message` and sends a choice of
+    /// a user to the `callback`. The dialog closes itself after the choice.
+    pub fn confirm_async(&mut self, message: &str, callback: Callback<bool>) -> DialogTask {
+        let closer: Closer = Rc::new(RefCell::new(None));
+        let on_close = closer.clone();
+        let on_choice = Callback::once(move |choice| {
+            let close = on_close.borrow_mut().take();
+            if let Some(close) = close {
+                close();
+            }
+            callback.emit(choice);
+        });
+        let props = confirm::Props {
+            message: message.to_owned(),
+            confirm_label: None,
+            cancel_label: None,
+            on_choice,
+        };
+        let DialogTask(opened) = self.open::<Confirm>(props);
+        let close = opened.borrow_mut().take();
+        *closer.borrow_mut() = close;
+        DialogTask(closer)
+    }
+
+    /// Opens a `Confirm` dialog like `confirm_async`, but returns a future
+    /// of a choice. Dropping the future closes the dialog. It's available
+    /// with the `futures` feature only.
+    #[cfg(feature = "futures")]
+    pub fn confirm_future(&mut self, message: &str) -> DialogFuture {
+        let state = Rc::new(RefCell::new(ChoiceState {
+            choice: None,
+            waker: None,
+        }));
+        let on_choice = state.clone();
+        let callback = move |choice| {
+            let waker = {
+                let mut state = on_choice.borrow_mut();
+                state.choice = Some(choice);
+                state.waker.take()
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        };
+        let task = self.confirm_async(message, callback.into());
+        DialogFuture { state, _task: task }
+    }
+}
+
+#[cfg(feature = "futures")]
+struct ChoiceState {
+    choice: Option<bool>,
+    waker: Option<Waker>,
+}
+
+/// A future of a choice of a user in a dialog.
+#[cfg(feature = "futures")]
+#[must_use]
+pub struct DialogFuture {
+    state: Rc<RefCell<ChoiceState>>,
+    _task: DialogTask,
+}
+
+#[cfg(feature = "futures")]
+impl Future for DialogFuture {
+    type Output = bool;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.borrow_mut();
+        match state.choice.take() {
+            Some(choice) => Poll::Ready(choice),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl Task for DialogTask {
+    fn is_active(&self) -> bool {
+        self.0.borrow().is_some()
+    }
+    fn cancel(&mut self) {
+        let close = self.0.borrow_mut().take();
+        let close = close.expect("tried to close a dialog twice");
+        close();
+    }
+}
+
+impl Drop for DialogTask {
+    fn drop(&mut self) {
+        if self.is_active() {
+            self.cancel();
+        }
+    }
 }
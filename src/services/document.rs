@@ -0,0 +1,213 @@
+//! This module contains a service to manage the title and tags
+//! in `<head>` of the document.
+//!
+//! Every declaration is kept until its task is dropped. When several
+//! declarations set the same tag the latest one wins, and previous values
+//! are restored when it's dropped. Tags which are declared in `index.html`
+//! are overridden in place and restored too.
+
+use super::Task;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use stdweb::unstable::TryInto;
+#[allow(unused_imports)]
+use stdweb::{_js_impl, js};
+
+/// Tags of `<head>` which a declaration sets.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HeadTags {
+    /// The title of the document.
+    pub title: Option<String>,
+    /// Pairs of `name` and `content` of `<meta>` tags.
+    pub meta: Vec<(String, String)>,
+    /// Pairs of `rel` and `href` of `<link>` tags.
+    pub links: Vec<(String, String)>,
+}
+
+impl HeadTags {
+    /// Creates an empty declaration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the title of the document.
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_owned());
+        self
+    }
+
+    /// Sets `content` of a `<meta>` tag with the `name`.
+    pub fn meta(mut self, name: &str, content: &str) -> Self {
+        self.meta.push((name.to_owned(), content.to_owned()));
+        self
+    }
+
+    /// Sets `href` of a `<link>` tag with the `rel`.
+    pub fn link(mut self, rel: &str, href: &str) -> Self {
+        self.links.push((rel.to_owned(), href.to_owned()));
+        self
+    }
+}
+
+#[derive(Default)]
+struct Declarations {
+    next_id: u64,
+    original_title: Option<String>,
+    entries: Vec<(u64, HeadTags)>,
+}
+
+thread_local! {
+    static DECLARATIONS: RefCell<Declarations> = RefCell::new(Declarations::default());
+}
+
+/// A handle which keeps a declaration of tags. The declaration
+/// is removed when the task is dropped.
+#[must_use]
+pub struct HeadTask(Option<u64>);
+
+/// A service to manage `<head>` of the document.
+#[derive(Default)]
+pub struct DocumentService {}
+
+impl DocumentService {
+    /// Creates a new service instance connected to `App` by provided `sender`.
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Returns the current title of the document.
+    pub fn title(&self) -> String {
+        get_title()
+    }
+
+    /// Declares tags of `<head>` until the returned task is dropped.
+    pub fn set_head(&mut self, head: HeadTags) -> HeadTask {
+        let id = DECLARATIONS.with(|declarations| {
+            let mut declarations = declarations.borrow_mut();
+            if declarations.original_title.is_none() {
+                declarations.original_title = Some(get_title());
+            }
+            let id = declarations.next_id;
+            declarations.next_id += 1;
+            declarations.entries.push((id, head));
+            id
+        });
+        apply();
+        HeadTask(Some(id))
+    }
+}
+
+impl HeadTask {
+    /// Replaces tags of the declaration. It keeps its priority
+    /// over declarations which were made after it.
+    pub fn update(&mut self, head: HeadTags) {
+        if let Some(id) = self.0 {
+            DECLARATIONS.with(|declarations| {
+                let mut declarations = declarations.borrow_mut();
+                if let Some(entry) = declarations.entries.iter_mut().find(|(i, _)| *i == id) {
+                    entry.1 = head;
+                }
+            });
+            apply();
+        }
+    }
+}
+
+fn get_title() -> String {
+    js! { return document.title; }
+        .try_into()
+        .unwrap_or_default()
+}
+
+/// Merges all declarations and writes them to the document.
+fn apply() {
+    let (title, meta, links) = DECLARATIONS.with(|declarations| {
+        let declarations = declarations.borrow();
+        let mut title = declarations.original_title.clone();
+        let mut meta: HashMap<&str, &str> = HashMap::new();
+        let mut links: HashMap<&str, &str> = HashMap::new();
+        for (_, head) in &declarations.entries {
+            if let Some(ref value) = head.title {
+                title = Some(value.clone());
+            }
+            for (name, content) in &head.meta {
+                meta.insert(name, content);
+            }
+            for (rel, href) in &head.links {
+                links.insert(rel, href);
+            }
+        }
+        let to_owned = |map: HashMap<&str, &str>| -> Vec<Vec<String>> {
+            map.into_iter()
+                .map(|(key, value)| vec![key.to_owned(), value.to_owned()])
+                .collect()
+        };
+        (title, to_owned(meta), to_owned(links))
+    });
+    js! { @(no_return)
+        var title = @{title};
+        if (title !== null) {
+            document.title = title;
+        }
+        var head = document.head;
+        var restore = head.querySelectorAll("[data-yew-original]");
+        for (var i = 0; i < restore.length; i++) {
+            var tag = restore[i];
+            var attr = tag.tagName === "META" ? "content" : "href";
+            tag.setAttribute(attr, tag.getAttribute("data-yew-original"));
+            tag.removeAttribute("data-yew-original");
+        }
+        var managed = head.querySelectorAll("[data-yew-head]");
+        for (var i = 0; i < managed.length; i++) {
+            head.removeChild(managed[i]);
+        }
+        var declare = function(tagName, keyAttr, valueAttr, pairs) {
+            var tags = head.getElementsByTagName(tagName);
+            pairs.forEach(function(pair) {
+                var found = null;
+                for (var i = 0; i < tags.length; i++) {
+                    if (tags[i].getAttribute(keyAttr) === pair[0]) {
+                        found = tags[i];
+                        break;
+                    }
+                }
+                if (found) {
+                    found.setAttribute("data-yew-original", found.getAttribute(valueAttr) || "");
+                    found.setAttribute(valueAttr, pair[1]);
+                } else {
+                    var tag = document.createElement(tagName);
+                    tag.setAttribute("data-yew-head", "");
+                    tag.setAttribute(keyAttr, pair[0]);
+                    tag.setAttribute(valueAttr, pair[1]);
+                    head.appendChild(tag);
+                }
+            });
+        };
+        declare("meta", "name", "content", @{meta});
+        declare("link", "rel", "href", @{links});
+    }
+}
+
+impl Task for HeadTask {
+    fn is_active(&self) -> bool {
+        self.0.is_some()
+    }
+    fn cancel(&mut self) {
+        let id = self
+            .0
+            .take()
+            .expect("tried to cancel head declaration twice");
+        DECLARATIONS.with(|declarations| {
+            declarations.borrow_mut().entries.retain(|(i, _)| *i != id);
+        });
+        apply();
+    }
+}
+
+impl Drop for HeadTask {
+    fn drop(&mut self) {
+        if self.is_active() {
+            self.cancel();
+        }
+    }
+}
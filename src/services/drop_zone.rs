@@ -0,0 +1,155 @@
+//! This module contains the implementation of a service which turns
+//! an element into a target to drop files.
+
+use super::Task;
+use crate::callback::Callback;
+use crate::html::{NodeRef, NodeRefListener};
+use stdweb::web::File;
+use stdweb::Value;
+#[allow(unused_imports)]
+use stdweb::{_js_impl, js};
+
+/// A handle which stops listening of drag events of an element.
+#[must_use]
+pub struct DropZoneTask {
+    handle: Option<Value>,
+    listener: Option<NodeRefListener>,
+}
+
+/// A service to receive files dropped on elements.
+#[derive(Default)]
+pub struct DropZoneService {}
+
+impl DropZoneService {
+    /// Creates a new service instance connected to `App` by provided `sender`.
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Makes the element of `node_ref` a drop target. Dropped files are sent
+    /// to `on_drop` and `on_hover` receives `true` when files are dragged over
+    /// the element and `false` when they leave it or were dropped.
+    /// If the element is not rendered yet it will be attached as soon as it appears.
+    /// When the element is replaced with a new one, the new element is attached instead.
+    pub fn attach(
+        &mut self,
+        node_ref: &NodeRef,
+        on_drop: Callback<Vec<File>>,
+        on_hover: Callback<bool>,
+    ) -> DropZoneTask {
+        let on_drop = move |files: Vec<File>| {
+            on_drop.emit(files);
+        };
+        let on_hover = move |hover: bool| {
+            on_hover.emit(hover);
+        };
+        let handle = js! {
+            var on_drop = @{on_drop};
+            var on_hover = @{on_hover};
+            // Counts entered children, because `dragleave` fires
+            // when a pointer moves from the element to its child.
+            var depth = 0;
+            var has_files = function(event) {
+                var types = event.dataTransfer && event.dataTransfer.types;
+                return types && Array.prototype.indexOf.call(types, "Files") !== -1;
+            };
+            var listeners = {
+                dragenter: function(event) {
+                    if (!has_files(event)) {
+                        return;
+                    }
+                    event.preventDefault();
+                    depth += 1;
+                    if (depth === 1) {
+                        on_hover(true);
+                    }
+                },
+                dragover: function(event) {
+                    if (!has_files(event)) {
+                        return;
+                    }
+                    // Without it a browser doesn't allow to drop.
+                    event.preventDefault();
+                    event.dataTransfer.dropEffect = "copy";
+                },
+                dragleave: function(event) {
+                    if (!has_files(event) || depth === 0) {
+                        return;
+                    }
+                    depth -= 1;
+                    if (depth === 0) {
+                        on_hover(false);
+                    }
+                },
+                drop: function(event) {
+                    if (!has_files(event)) {
+                        return;
+                    }
+                    // Without it a browser opens the file.
+                    event.preventDefault();
+                    if (depth !== 0) {
+                        depth = 0;
+                        on_hover(false);
+                    }
+                    on_drop(Array.prototype.slice.call(event.dataTransfer.files));
+                },
+            };
+            var handle = {
+                node: null,
+                listeners: listeners,
+                callbacks: [on_drop, on_hover],
+                attach: function(node) {
+                    if (handle.node !== null) {
+                        var old = handle.node;
+                        Object.keys(listeners).forEach(function(kind) {
+                            old.removeEventListener(kind, listeners[kind]);
+                        });
+                    }
+                    depth = 0;
+                    handle.node = node;
+                    if (node !== null) {
+                        Object.keys(listeners).forEach(function(kind) {
+                            node.addEventListener(kind, listeners[kind]);
+                        });
+                    }
+                },
+            };
+            return handle;
+        };
+        let attach = handle.clone();
+        let listener = node_ref.listen(move |node| {
+            js! { @(no_return)
+                @{&attach}.attach(@{node});
+            }
+        });
+        DropZoneTask {
+            handle: Some(handle),
+            listener: Some(listener),
+        }
+    }
+}
+
+impl Task for DropZoneTask {
+    fn is_active(&self) -> bool {
+        self.handle.is_some()
+    }
+    fn cancel(&mut self) {
+        let handle = self.handle.take().expect("tried to cancel drop zone twice");
+        self.listener.take();
+        js! { @(no_return)
+            var handle = @{handle};
+            handle.attach(null);
+            handle.callbacks.forEach(function(callback) {
+                callback.drop();
+            });
+        }
+    }
+}
+
+impl Drop for DropZoneTask {
+    fn drop(&mut self) {
+        if self.is_active() {
+            self.cancel();
+        }
+    }
+}
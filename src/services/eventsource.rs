@@ -0,0 +1,112 @@
+//! Service to consume
+//! [Server-Sent Events](https://html.spec.whatwg.org/multipage/server-sent-events.html).
+
+use super::Task;
+use crate::callback::Callback;
+use crate::format::Text;
+use stdweb::unstable::TryInto;
+use stdweb::Value;
+#[allow(unused_imports)]
+use stdweb::{_js_impl, js};
+
+#[derive(Debug)]
+/// A status of an event source connection. Used for status notification.
+pub enum EventSourceStatus {
+    /// Fired when a connection was opened (or reopened by a browser).
+    Opened,
+    /// Fired when a connection failed. A browser tries to reconnect
+    /// automatically unless the task is closed.
+    Error,
+}
+
+/// A handle to control current event source connection. Implements `Task` and could be canceled.
+#[must_use]
+pub struct EventSourceTask(Option<Value>);
+
+/// An event source service attached to a user context.
+#[derive(Default)]
+pub struct EventSourceService {}
+
+impl EventSourceService {
+    /// Creates a new service instance connected to `App` by provided `sender`.
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Connects to a server which sends events. Needs two functions to generate
+    /// data and notification messages. Only unnamed (`message`) events are delivered.
+    pub fn connect<OUT: 'static>(
+        &mut self,
+        url: &str,
+        callback: Callback<OUT>,
+        notification: Callback<EventSourceStatus>,
+    ) -> EventSourceTask
+    where
+        OUT: From<Text>,
+    {
+        let on_message = move |data: String| {
+            let out = OUT::from(Ok(data));
+            callback.emit(out);
+        };
+        let notify = notification.clone();
+        let on_open = move || {
+            notify.emit(EventSourceStatus::Opened);
+        };
+        let on_error = move || {
+            notification.emit(EventSourceStatus::Error);
+        };
+        let handle = js! {
+            var on_message = @{on_message};
+            var on_open = @{on_open};
+            var on_error = @{on_error};
+            var source = new EventSource(@{url});
+            source.onmessage = function(event) {
+                on_message(event.data);
+            };
+            source.onopen = function() {
+                on_open();
+            };
+            source.onerror = function() {
+                on_error();
+            };
+            return {
+                source: source,
+                callbacks: [on_message, on_open, on_error],
+            };
+        };
+        EventSourceTask(Some(handle))
+    }
+}
+
+impl Task for EventSourceTask {
+    fn is_active(&self) -> bool {
+        if let Some(ref handle) = self.0 {
+            // `CLOSED` means a browser gave up to reconnect.
+            js! {
+                return @{handle}.source.readyState !== EventSource.CLOSED;
+            }
+            .try_into()
+            .unwrap_or(false)
+        } else {
+            false
+        }
+    }
+    fn cancel(&mut self) {
+        let handle = self.0.take().expect("tried to cancel event source twice");
+        js! { @(no_return)
+            var handle = @{handle};
+            handle.source.close();
+            handle.callbacks.forEach(function(callback) {
+                callback.drop();
+            });
+        }
+    }
+}
+
+impl Drop for EventSourceTask {
+    fn drop(&mut self) {
+        if self.0.is_some() {
+            self.cancel();
+        }
+    }
+}
@@ -1,11 +1,12 @@
 //! Service to send HTTP-request to a server.
 
-use super::Task;
+use super::{to_ms, Task};
 use crate::callback::Callback;
-use crate::format::{Binary, Format, Text};
+use crate::format::{Binary, Encoded, Nothing, Text};
 use failure::Fail;
 use serde::Serialize;
 use std::collections::HashMap;
+use std::time::Duration;
 use stdweb::serde::Serde;
 use stdweb::unstable::{TryFrom, TryInto};
 use stdweb::web::ArrayBuffer;
@@ -29,10 +30,27 @@ pub enum Credentials {
 
 /// Init options for `fetch()` function call.
 /// https://developer.mozilla.org/en-US/docs/Web/API/WindowOrWorkerGlobalScope/fetch
-#[derive(Serialize)]
+#[derive(Serialize, Default)]
 pub struct FetchOptions {
     /// Credentials of a fetch request.
     pub credentials: Option<Credentials>,
+    /// Aborts the request if the response wasn't received in time.
+    /// The callback gets a response with `408` status in that case.
+    #[serde(skip)]
+    pub timeout: Option<Duration>,
+    /// Callback to track the downloading of a response body.
+    /// Fetch API can't track the uploading of a request body.
+    #[serde(skip)]
+    pub progress: Option<Callback<FetchProgress>>,
+}
+
+/// Progress of the downloading of a response body.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FetchProgress {
+    /// Amount of received bytes.
+    pub loaded: u64,
+    /// Total amount of bytes if a server provided `Content-Length` header.
+    pub total: Option<u64>,
 }
 
 /// Represents errors of a fetch service.
@@ -42,10 +60,31 @@ enum FetchError {
     FailedResponse,
 }
 
+/// Events of a response which is received in streaming mode.
+pub enum FetchStreamEvent {
+    /// Status and headers of the response had received.
+    Started(Response<Nothing>),
+    /// A chunk of the response body had received.
+    Chunk(Vec<u8>),
+    /// The response body had received completely.
+    Finished,
+    /// The request or reading of the response body failed.
+    Failed,
+}
+
 /// A handle to control sent requests. Can be canceled with a `Task::cancel` call.
 #[must_use]
 pub struct FetchTask(Option<Value>);
 
+impl FetchTask {
+    /// Aborts the request if it's still active. The callback won't be called.
+    pub fn abort(&mut self) {
+        if self.is_active() {
+            self.cancel();
+        }
+    }
+}
+
 /// A service to fetch resources.
 #[derive(Default)]
 pub struct FetchService {}
@@ -119,12 +158,15 @@ impl FetchService {
     }
 
     /// `fetch` with provided `FetchOptions` object.
-    /// Use it if you need to send cookies with a request:
+    /// Use it if you need to send cookies with a request, to set a timeout
+    /// or to track progress:
     /// ```rust
     ///     let request = fetch::Request::get("/path/")
     ///         .body(Nothing).unwrap();
     ///     let options = FetchOptions {
     ///         credentials: Some(Credentials::SameOrigin),
+    ///         timeout: Some(Duration::from_secs(10)),
+    ///         ..FetchOptions::default()
     ///     };
     ///     let task = fetch_service.fetch_with_options(request, options, callback);
     /// ```
@@ -142,6 +184,8 @@ impl FetchService {
     }
 
     /// Fetch the data in binary format.
+    /// Use `Binary` type as a body of request or response to send
+    /// or receive raw bytes without any serialization.
     pub fn fetch_binary<IN, OUT: 'static>(
         &mut self,
         request: Request<IN>,
@@ -167,40 +211,60 @@ impl FetchService {
     {
         fetch_impl::<IN, OUT, Vec<u8>, ArrayBuffer>(true, request, Some(options), callback)
     }
+
+    /// Fetch the data in streaming mode. Chunks of the response body are
+    /// passed to the callback as they arrive and aren't kept by the service.
+    /// Useful to download large files or to consume NDJSON streams.
+    pub fn fetch_stream<IN>(
+        &mut self,
+        request: Request<IN>,
+        callback: Callback<FetchStreamEvent>,
+    ) -> FetchTask
+    where
+        IN: Into<Binary>,
+    {
+        fetch_stream_impl(request, None, callback)
+    }
+
+    /// `fetch_stream` with provided `FetchOptions` object.
+    /// Progress callback of options is ignored in streaming mode.
+    pub fn fetch_stream_with_options<IN>(
+        &mut self,
+        request: Request<IN>,
+        options: FetchOptions,
+        callback: Callback<FetchStreamEvent>,
+    ) -> FetchTask
+    where
+        IN: Into<Binary>,
+    {
+        fetch_stream_impl(request, Some(options), callback)
+    }
 }
 
 fn fetch_impl<IN, OUT: 'static, T, X>(
     binary: bool,
     request: Request<IN>,
-    options: Option<FetchOptions>,
+    mut options: Option<FetchOptions>,
     callback: Callback<Response<OUT>>,
 ) -> FetchTask
 where
-    IN: Into<Format<T>>,
-    OUT: From<Format<T>>,
+    IN: Into<Encoded<T>>,
+    OUT: From<Encoded<T>>,
     T: JsSerialize,
     X: TryFrom<Value> + Into<T>,
 {
-    // Consume request as parts and body.
-    let (parts, body) = request.into_parts();
-
-    // Map headers into a Js serializable HashMap.
-    let header_map: HashMap<&str, &str> = parts
-        .headers
-        .iter()
-        .map(|(k, v)| {
-            (
-                k.as_str(),
-                v.to_str()
-                    .expect(format!("Unparsable request header {}: {:?}", k.as_str(), v).as_str()),
-            )
-        })
-        .collect();
-
-    // Formats URI.
-    let uri = format!("{}", parts.uri);
-    let method = parts.method.as_str();
-    let body = body.into().ok();
+    // Progress is handled by the service and not by `fetch()`.
+    let progress = options.as_mut().and_then(|options| options.progress.take());
+    let has_progress = progress.is_some();
+    let progress = move |loaded: f64, total: f64| {
+        if let Some(ref progress) = progress {
+            let state = FetchProgress {
+                loaded: loaded as u64,
+                total: Some(total as u64).filter(|total| *total > 0),
+            };
+            progress.emit(state);
+        }
+    };
 
     // Prepare the response callback.
     // Notice that the callback signature must match the call from the javascript
@@ -223,6 +287,195 @@ where
         callback.emit(response);
     };
 
+    let reader = js! {
+        var binary = @{binary};
+        var callback = @{callback};
+        var progress = @{progress};
+        var hasProgress = @{has_progress};
+        var empty = function() {
+            return binary ? new ArrayBuffer() : "";
+        };
+        var readBody = function(response, handle) {
+            if (!hasProgress || !response.body || !response.body.getReader) {
+                return binary ? response.arrayBuffer() : response.text();
+            }
+            var total = parseInt(response.headers.get("Content-Length")) || 0;
+            var reader = response.body.getReader();
+            var chunks = [];
+            var loaded = 0;
+            var pump = function() {
+                return reader.read().then(function(result) {
+                    if (result.done) {
+                        var all = new Uint8Array(loaded);
+                        var offset = 0;
+                        chunks.forEach(function(chunk) {
+                            all.set(chunk, offset);
+                            offset += chunk.length;
+                        });
+                        return binary ? all.buffer : new TextDecoder("utf-8").decode(all);
+                    }
+                    chunks.push(result.value);
+                    loaded += result.value.length;
+                    if (handle.active == true) {
+                        progress(loaded, total);
+                    }
+                    return pump();
+                });
+            };
+            return pump();
+        };
+        return {
+            callbacks: [callback, progress],
+            done: callback,
+            fail: function(handle) {
+                handle.finish(false, 408, {}, empty());
+            },
+            read: function(response, handle) {
+                var status = response.status;
+                var headers = {};
+                response.headers.forEach(function(value, key) {
+                    headers[key] = value;
+                });
+                readBody(response, handle).then(function(data) {
+                    handle.finish(true, status, headers, data);
+                }).catch(function(err) {
+                    handle.finish(false, status, headers, empty());
+                });
+            },
+        };
+    };
+    send_request::<IN, T>(binary, request, options, reader)
+}
+
+fn fetch_stream_impl<IN>(
+    request: Request<IN>,
+    options: Option<FetchOptions>,
+    callback: Callback<FetchStreamEvent>,
+) -> FetchTask
+where
+    IN: Into<Binary>,
+{
+    let started = {
+        let callback = callback.clone();
+        move |status: u16, headers: HashMap<String, String>| {
+            let mut response_builder = Response::builder();
+            response_builder.status(status);
+            for (key, values) in &headers {
+                response_builder.header(key.as_str(), values.as_str());
+            }
+            let response = response_builder.body(Nothing).unwrap();
+            callback.emit(FetchStreamEvent::Started(response));
+        }
+    };
+    let chunk = {
+        let callback = callback.clone();
+        move |data: ArrayBuffer| {
+            callback.emit(FetchStreamEvent::Chunk(data.into()));
+        }
+    };
+    let finished = move |success: bool| {
+        if success {
+            callback.emit(FetchStreamEvent::Finished);
+        } else {
+            callback.emit(FetchStreamEvent::Failed);
+        }
+    };
+
+    let reader = js! {
+        var started = @{started};
+        var chunk = @{chunk};
+        var finished = @{finished};
+        return {
+            callbacks: [started, chunk, finished],
+            done: finished,
+            fail: function(handle) {
+                handle.finish(false);
+            },
+            read: function(response, handle) {
+                if (handle.active != true) {
+                    return;
+                }
+                var headers = {};
+                response.headers.forEach(function(value, key) {
+                    headers[key] = value;
+                });
+                started(response.status, headers);
+                if (!response.body || !response.body.getReader) {
+                    return response.arrayBuffer().then(function(data) {
+                        if (handle.active == true) {
+                            chunk(data);
+                        }
+                        handle.finish(true);
+                    });
+                }
+                var reader = response.body.getReader();
+                var pump = function() {
+                    return reader.read().then(function(result) {
+                        if (handle.active != true) {
+                            reader.cancel();
+                            return;
+                        }
+                        if (result.done) {
+                            handle.finish(true);
+                            return;
+                        }
+                        var value = result.value;
+                        chunk(value.buffer.slice(value.byteOffset, value.byteOffset + value.byteLength));
+                        return pump();
+                    });
+                };
+                return pump();
+            },
+        };
+    };
+    send_request::<IN, Vec<u8>>(true, request, options, reader)
+}
+
+/// Sends a request with `fetch()` for all modes of the service. It builds
+/// the request, aborts it by a timeout and keeps the state of the task.
+///
+/// The `reader` is a JS object which implements a mode:
+/// * `callbacks` are dropped when the task finished or canceled;
+/// * `read(response, handle)` consumes the response and calls `handle.finish`;
+/// * `fail(handle)` is called if the request failed or timed out;
+/// * `done` gets arguments of the first `handle.finish` call.
+fn send_request<IN, T>(
+    binary: bool,
+    request: Request<IN>,
+    options: Option<FetchOptions>,
+    reader: Value,
+) -> FetchTask
+where
+    IN: Into<Encoded<T>>,
+    T: JsSerialize,
+{
+    // Consume request as parts and body.
+    let (parts, body) = request.into_parts();
+
+    // Map headers into a Js serializable HashMap.
+    let header_map: HashMap<&str, &str> = parts
+        .headers
+        .iter()
+        .map(|(k, v)| {
+            (
+                k.as_str(),
+                v.to_str()
+                    .expect(format!("Unparsable request header {}: {:?}", k.as_str(), v).as_str()),
+            )
+        })
+        .collect();
+
+    // Formats URI.
+    let uri = format!("{}", parts.uri);
+    let method = parts.method.as_str();
+    let body = body.into().ok();
+
+    // Timeout is handled by the service and not by `fetch()`.
+    let timeout = options
+        .as_ref()
+        .and_then(|options| options.timeout)
+        .map(to_ms);
+
     let handle = js! {
         var body = @{body};
         if (@{binary} && body != null) {
@@ -234,44 +487,44 @@ where
             headers: @{header_map},
         };
         var request = new Request(@{uri}, data);
-        var callback = @{callback};
+        var reader = @{reader};
+        var timeout = @{timeout};
         var abortController = AbortController ? new AbortController() : null;
         var handle = {
             active: true,
-            callback,
+            callbacks: reader.callbacks,
             abortController,
+            timeoutId: null,
         };
         var init = @{Serde(options)} || {};
         if (abortController && !("signal" in init)) {
             init.signal = abortController.signal;
         }
-        fetch(request, init).then(function(response) {
-            var promise = (@{binary}) ? response.arrayBuffer() : response.text();
-            var status = response.status;
-            var headers = {};
-            response.headers.forEach(function(value, key) {
-                headers[key] = value;
-            });
-            promise.then(function(data) {
-                if (handle.active == true) {
-                    handle.active = false;
-                    callback(true, status, headers, data);
-                    callback.drop();
+        handle.finish = function() {
+            if (handle.active == true) {
+                handle.active = false;
+                if (handle.timeoutId != null) {
+                    clearTimeout(handle.timeoutId);
                 }
-            }).catch(function(err) {
-                if (handle.active == true) {
-                    handle.active = false;
-                    callback(false, status, headers, data);
+                reader.done.apply(null, arguments);
+                handle.callbacks.forEach(function(callback) {
                     callback.drop();
+                });
+            }
+        };
+        if (timeout != null) {
+            handle.timeoutId = setTimeout(function() {
+                handle.timeoutId = null;
+                if (abortController) {
+                    abortController.abort();
                 }
-            });
+                reader.fail(handle);
+            }, timeout);
+        }
+        fetch(request, init).then(function(response) {
+            return reader.read(response, handle);
         }).catch(function(e) {
-            if (handle.active == true) {
-                var data = (@{binary}) ? new ArrayBuffer() : "";
-                handle.active = false;
-                callback(false, 408, {}, data);
-                callback.drop();
-            }
+            reader.fail(handle);
         });
         return handle;
     };
@@ -302,7 +555,12 @@ impl Task for FetchTask {
         js! {  @(no_return)
             var handle = @{handle};
             handle.active = false;
-            handle.callback.drop();
+            handle.callbacks.forEach(function(callback) {
+                callback.drop();
+            });
+            if (handle.timeoutId != null) {
+                clearTimeout(handle.timeoutId);
+            }
             if (handle.abortController) {
                 handle.abortController.abort();
             }
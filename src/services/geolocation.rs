@@ -0,0 +1,207 @@
+//! This module contains the implementation of a service to get
+//! [position](https://developer.mozilla.org/en-US/docs/Web/API/Geolocation_API)
+//! of a device.
+
+use super::{to_ms, Task};
+use crate::callback::Callback;
+use failure::Fail;
+use std::time::Duration;
+use stdweb::Value;
+#[allow(unused_imports)]
+use stdweb::{_js_impl, js};
+
+/// A position of a device.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Position {
+    /// Latitude in decimal degrees.
+    pub latitude: f64,
+    /// Longitude in decimal degrees.
+    pub longitude: f64,
+    /// Accuracy of latitude and longitude in meters.
+    pub accuracy: f64,
+    /// Altitude in meters relative to sea level, if available.
+    pub altitude: Option<f64>,
+    /// Accuracy of altitude in meters, if available.
+    pub altitude_accuracy: Option<f64>,
+    /// Direction of movement in degrees clockwise from north, if available.
+    pub heading: Option<f64>,
+    /// Speed in meters per second, if available.
+    pub speed: Option<f64>,
+    /// Time of the position in milliseconds since the epoch.
+    pub timestamp: f64,
+}
+
+/// Represents errors of geolocation.
+#[derive(Debug, Fail, Clone, PartialEq)]
+pub enum GeolocationError {
+    /// A user denied access to the position.
+    #[fail(display = "permission denied")]
+    PermissionDenied,
+    /// A device can't determine the position.
+    #[fail(display = "position unavailable: {}", _0)]
+    PositionUnavailable(String),
+    /// The position wasn't determined in time.
+    #[fail(display = "timeout expired")]
+    Timeout,
+}
+
+/// Options of position requests.
+#[derive(Debug, Clone, Default)]
+pub struct GeolocationOptions {
+    /// Requests the best possible position. Slower and consumes more power.
+    pub high_accuracy: bool,
+    /// Maximum time to determine the position.
+    pub timeout: Option<Duration>,
+    /// Maximum age of a cached position which is acceptable.
+    pub maximum_age: Option<Duration>,
+}
+
+/// A handle of a position request or a watch. Clears the watch when canceled.
+#[must_use]
+pub struct GeolocationTask(Option<Value>);
+
+/// A service to get position of a device.
+#[derive(Default)]
+pub struct GeolocationService {}
+
+impl GeolocationService {
+    /// Creates a new service instance connected to `App` by provided `sender`.
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Requests the current position once.
+    pub fn get_position(
+        &mut self,
+        options: GeolocationOptions,
+        callback: Callback<Result<Position, GeolocationError>>,
+    ) -> GeolocationTask {
+        self.spawn(options, callback, false)
+    }
+
+    /// Sends the position to `callback` every time it changes.
+    pub fn watch_position(
+        &mut self,
+        options: GeolocationOptions,
+        callback: Callback<Result<Position, GeolocationError>>,
+    ) -> GeolocationTask {
+        self.spawn(options, callback, true)
+    }
+
+    fn spawn(
+        &mut self,
+        options: GeolocationOptions,
+        callback: Callback<Result<Position, GeolocationError>>,
+        watch: bool,
+    ) -> GeolocationTask {
+        let on_position = {
+            let callback = callback.clone();
+            move |coords: Vec<Option<f64>>| {
+                let position = Position {
+                    latitude: coords[0].unwrap_or_default(),
+                    longitude: coords[1].unwrap_or_default(),
+                    accuracy: coords[2].unwrap_or_default(),
+                    altitude: coords[3],
+                    altitude_accuracy: coords[4],
+                    heading: coords[5],
+                    speed: coords[6],
+                    timestamp: coords[7].unwrap_or_default(),
+                };
+                callback.emit(Ok(position));
+            }
+        };
+        let on_error = move |code: u32, message: String| {
+            let error = match code {
+                1 => GeolocationError::PermissionDenied,
+                3 => GeolocationError::Timeout,
+                _ => GeolocationError::PositionUnavailable(message),
+            };
+            callback.emit(Err(error));
+        };
+        let timeout = options.timeout.map(to_ms);
+        let maximum_age = options.maximum_age.map(to_ms).unwrap_or(0);
+        let handle = js! {
+            var on_position = @{on_position};
+            var on_error = @{on_error};
+            var watch = @{watch};
+            var handle = {
+                watch_id: null,
+                callbacks: [on_position, on_error],
+                active: true,
+            };
+            var finish = function() {
+                if (!watch && handle.active) {
+                    handle.active = false;
+                    on_position.drop();
+                    on_error.drop();
+                }
+            };
+            var success = function(position) {
+                var coords = position.coords;
+                on_position([
+                    coords.latitude,
+                    coords.longitude,
+                    coords.accuracy,
+                    coords.altitude,
+                    coords.altitudeAccuracy,
+                    coords.heading,
+                    coords.speed,
+                    position.timestamp,
+                ]);
+                finish();
+            };
+            var failure = function(error) {
+                on_error(error.code, error.message);
+                finish();
+            };
+            var options = {
+                enableHighAccuracy: @{options.high_accuracy},
+                maximumAge: @{maximum_age},
+            };
+            var timeout = @{timeout};
+            if (timeout !== null) {
+                options.timeout = timeout;
+            }
+            if (!navigator.geolocation) {
+                Promise.resolve().then(function() {
+                    failure({ code: 2, message: "geolocation is not supported" });
+                });
+            } else if (watch) {
+                handle.watch_id = navigator.geolocation.watchPosition(success, failure, options);
+            } else {
+                navigator.geolocation.getCurrentPosition(success, failure, options);
+            }
+            return handle;
+        };
+        GeolocationTask(Some(handle))
+    }
+}
+
+impl Task for GeolocationTask {
+    fn is_active(&self) -> bool {
+        self.0.is_some()
+    }
+    fn cancel(&mut self) {
+        let handle = self.0.take().expect("tried to cancel geolocation twice");
+        js! { @(no_return)
+            var handle = @{handle};
+            if (handle.watch_id !== null) {
+                navigator.geolocation.clearWatch(handle.watch_id);
+            }
+            if (handle.active) {
+                handle.active = false;
+                handle.callbacks.forEach(function(callback) {
+                    callback.drop();
+                });
+            }
+        }
+    }
+}
+
+impl Drop for GeolocationTask {
+    fn drop(&mut self) {
+        if self.is_active() {
+            self.cancel();
+        }
+    }
+}
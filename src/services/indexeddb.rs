@@ -0,0 +1,442 @@
+//! This module contains the implementation of a service to keep structured
+//! data in [IndexedDB](https://developer.mozilla.org/en-US/docs/Web/API/IndexedDB_API).
+//!
+//! Values are stored as strings produced by a format, use `Json` to keep
+//! any serializable type:
+//!
+//! ```rust,ignore
+//! let task = db.put("users", &user.id, Json(&user), self.link.send_back(Msg::Stored));
+//! ```
+
+use super::Task;
+use crate::callback::Callback;
+use crate::format::Text;
+use failure::{Error, Fail};
+use std::cell::RefCell;
+use stdweb::unstable::TryInto;
+use stdweb::Value;
+#[allow(unused_imports)]
+use stdweb::{_js_impl, js};
+
+/// Represents errors of a database.
+#[derive(Debug, Fail)]
+pub enum IndexedDbError {
+    /// The key is not exists in an object store.
+    #[fail(display = "key not found")]
+    NotFound,
+    /// The database rejected a request. Contains the name and
+    /// the message of the `DOMException`.
+    #[fail(display = "request failed: {}", _0)]
+    Failed(String),
+    /// The database can't be upgraded, because it's opened with an older
+    /// version in another tab.
+    #[fail(display = "database is blocked by another connection")]
+    Blocked,
+}
+
+/// Converts an exception of a request to an error, `null` means success.
+fn request_error(error: Value) -> Option<IndexedDbError> {
+    match error {
+        Value::Null | Value::Undefined => None,
+        error => {
+            let message: String = js! {
+                var error = @{error};
+                return error.name ? error.name + ": " + error.message : String(error);
+            }
+            .try_into()
+            .unwrap_or_default();
+            Some(IndexedDbError::Failed(message))
+        }
+    }
+}
+
+/// A change of a database schema applied when the database
+/// upgrades to a version.
+#[derive(Debug, Clone)]
+pub struct Migration {
+    version: u32,
+    create: Vec<String>,
+    delete: Vec<String>,
+}
+
+impl Migration {
+    /// Creates an empty migration to `version`. Versions start from `1`.
+    pub fn new(version: u32) -> Self {
+        Migration {
+            version,
+            create: Vec::new(),
+            delete: Vec::new(),
+        }
+    }
+
+    /// Creates an object store in this version.
+    pub fn create_store(mut self, name: &str) -> Self {
+        self.create.push(name.to_owned());
+        self
+    }
+
+    /// Deletes an object store in this version.
+    pub fn delete_store(mut self, name: &str) -> Self {
+        self.delete.push(name.to_owned());
+        self
+    }
+}
+
+/// An opened database. Cheap to clone.
+#[derive(Clone, Debug)]
+pub struct IndexedDb {
+    db: Value,
+}
+
+/// A handle to a pending request. The callback won't be called if
+/// the task was canceled, but the request itself can't be aborted.
+#[must_use]
+pub struct IndexedDbTask(Option<Value>);
+
+/// A service to open IndexedDB databases.
+#[derive(Default)]
+pub struct IndexedDbService {}
+
+impl IndexedDbService {
+    /// Creates a new service instance connected to `App` by provided `sender`.
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Opens a database and upgrades it by `migrations` which are newer
+    /// than the current version of the database. The version of the
+    /// database is the greatest version of `migrations`.
+    ///
+    /// An opened database is closed when another tab upgrades it. If a tab
+    /// keeps an older version opened, the callback gets `Blocked` error.
+    pub fn open(
+        &mut self,
+        name: &str,
+        migrations: Vec<Migration>,
+        callback: Callback<Result<IndexedDb, Error>>,
+    ) -> IndexedDbTask {
+        let version = migrations
+            .iter()
+            .map(|migration| migration.version)
+            .max()
+            .unwrap_or(1);
+        let mut migrations = migrations;
+        migrations.sort_by_key(|migration| migration.version);
+        let versions: Vec<u32> = migrations.iter().map(|m| m.version).collect();
+        let creates: Vec<Vec<String>> = migrations.iter().map(|m| m.create.clone()).collect();
+        let deletes: Vec<Vec<String>> = migrations.iter().map(|m| m.delete.clone()).collect();
+        let callback = move |db: Value, error: Value, blocked: bool| {
+            let result = match request_error(error) {
+                _ if blocked => Err(IndexedDbError::Blocked.into()),
+                Some(error) => Err(error.into()),
+                None => Ok(IndexedDb { db }),
+            };
+            callback.emit(result);
+        };
+        let handle = js! {
+            var callback = @{callback};
+            var versions = @{versions};
+            var creates = @{creates};
+            var deletes = @{deletes};
+            var handle = { active: true, callbacks: [callback] };
+            var done = function(db, error, blocked) {
+                if (handle.active) {
+                    handle.active = false;
+                    callback(db, error, blocked);
+                    callback.drop();
+                    return true;
+                }
+                return false;
+            };
+            try {
+                // Throws if the version is `0`
+                var request = indexedDB.open(@{name}, @{version});
+            } catch (error) {
+                Promise.resolve().then(function() {
+                    done(null, error, false);
+                });
+                return handle;
+            }
+            request.onupgradeneeded = function(event) {
+                var db = request.result;
+                for (var i = 0; i < versions.length; i++) {
+                    if (versions[i] <= event.oldVersion) {
+                        continue;
+                    }
+                    deletes[i].forEach(function(store) {
+                        db.deleteObjectStore(store);
+                    });
+                    creates[i].forEach(function(store) {
+                        db.createObjectStore(store);
+                    });
+                }
+            };
+            request.onblocked = function() {
+                done(null, null, true);
+            };
+            request.onsuccess = function() {
+                var db = request.result;
+                db.onversionchange = function() {
+                    db.close();
+                };
+                // The request was blocked or canceled, but opened the database later
+                if (!done(db, null, false)) {
+                    db.close();
+                }
+            };
+            request.onerror = function() {
+                done(null, request.error, false);
+            };
+            return handle;
+        };
+        IndexedDbTask(Some(handle))
+    }
+}
+
+impl IndexedDb {
+    /// Reads a value of the `key` from the `store`.
+    pub fn get<OUT: 'static>(
+        &self,
+        store: &str,
+        key: &str,
+        callback: Callback<OUT>,
+    ) -> IndexedDbTask
+    where
+        OUT: From<Text>,
+    {
+        let callback = move |value: Option<String>, error: Value| {
+            let data = match (value, request_error(error)) {
+                (_, Some(error)) => Err(error.into()),
+                (Some(value), None) => Ok(value),
+                (None, None) => Err(IndexedDbError::NotFound.into()),
+            };
+            callback.emit(OUT::from(data));
+        };
+        let handle = js! {
+            var callback = @{callback};
+            var handle = { active: true, callbacks: [callback] };
+            var done = function(value, error) {
+                if (handle.active) {
+                    handle.active = false;
+                    callback(value, error);
+                    callback.drop();
+                }
+            };
+            try {
+                var store = @{store};
+                var request = @{&self.db}.transaction(store, "readonly").objectStore(store).get(@{key});
+                request.onsuccess = function() {
+                    var value = request.result;
+                    done(value === undefined ? null : value, null);
+                };
+                request.onerror = function() {
+                    done(null, request.error);
+                };
+            } catch (error) {
+                Promise.resolve().then(function() {
+                    done(null, error);
+                });
+            }
+            return handle;
+        };
+        IndexedDbTask(Some(handle))
+    }
+
+    /// Writes a value of the `key` to the `store`. Replaces an existing value.
+    pub fn put<IN>(
+        &self,
+        store: &str,
+        key: &str,
+        value: IN,
+        callback: Callback<Result<(), Error>>,
+    ) -> IndexedDbTask
+    where
+        IN: Into<Text>,
+    {
+        let value = match value.into() {
+            Ok(value) => value,
+            Err(error) => return Self::fail_later(error, callback),
+        };
+        // An exception is thrown if the store doesn't exist,
+        // it's returned instead of the request in that case.
+        let request = js! {
+            try {
+                var store = @{store};
+                return @{&self.db}.transaction(store, "readwrite").objectStore(store).put(@{value}, @{key});
+            } catch (error) {
+                return { exception: error };
+            }
+        };
+        Self::watch_write(request, callback)
+    }
+
+    /// Deletes a value of the `key` from the `store`.
+    pub fn delete(
+        &self,
+        store: &str,
+        key: &str,
+        callback: Callback<Result<(), Error>>,
+    ) -> IndexedDbTask {
+        let request = js! {
+            try {
+                var store = @{store};
+                return @{&self.db}.transaction(store, "readwrite").objectStore(store).delete(@{key});
+            } catch (error) {
+                return { exception: error };
+            }
+        };
+        Self::watch_write(request, callback)
+    }
+
+    /// Iterates over all values of the `store` in the order of keys.
+    /// Every pair of a key and a value is sent to `callback`, then `done` is called.
+    pub fn iterate<OUT: 'static>(
+        &self,
+        store: &str,
+        callback: Callback<(String, OUT)>,
+        done: Callback<Result<(), Error>>,
+    ) -> IndexedDbTask
+    where
+        OUT: From<Text>,
+    {
+        let on_item = move |key: String, value: String| {
+            callback.emit((key, OUT::from(Ok(value))));
+        };
+        let on_done = move |error: Value| {
+            let result = match request_error(error) {
+                Some(error) => Err(error.into()),
+                None => Ok(()),
+            };
+            done.emit(result);
+        };
+        let handle = js! {
+            var on_item = @{on_item};
+            var on_done = @{on_done};
+            var handle = { active: true, callbacks: [on_item, on_done] };
+            var done = function(error) {
+                if (handle.active) {
+                    handle.active = false;
+                    on_done(error);
+                    on_item.drop();
+                    on_done.drop();
+                }
+            };
+            try {
+                var store = @{store};
+                var request = @{&self.db}.transaction(store, "readonly").objectStore(store).openCursor();
+                request.onsuccess = function() {
+                    var cursor = request.result;
+                    if (!handle.active) {
+                        return;
+                    }
+                    if (cursor) {
+                        on_item(String(cursor.key), cursor.value);
+                        cursor.continue();
+                    } else {
+                        done(null);
+                    }
+                };
+                request.onerror = function() {
+                    done(request.error);
+                };
+            } catch (error) {
+                Promise.resolve().then(function() {
+                    done(error);
+                });
+            }
+            return handle;
+        };
+        IndexedDbTask(Some(handle))
+    }
+
+    /// Sends a result of a write `request` to `callback`.
+    fn watch_write(request: Value, callback: Callback<Result<(), Error>>) -> IndexedDbTask {
+        let callback = move |error: Value| {
+            let result = match request_error(error) {
+                Some(error) => Err(error.into()),
+                None => Ok(()),
+            };
+            callback.emit(result);
+        };
+        let handle = js! {
+            var callback = @{callback};
+            var request = @{request};
+            var handle = { active: true, callbacks: [callback] };
+            var done = function(error) {
+                if (handle.active) {
+                    handle.active = false;
+                    callback(error);
+                    callback.drop();
+                }
+            };
+            if ("exception" in request) {
+                Promise.resolve().then(function() {
+                    done(request.exception);
+                });
+            } else {
+                request.onsuccess = function() {
+                    done(null);
+                };
+                request.onerror = function() {
+                    done(request.error);
+                };
+            }
+            return handle;
+        };
+        IndexedDbTask(Some(handle))
+    }
+
+    /// Sends an error to `callback` later like errors of requests.
+    fn fail_later(error: Error, callback: Callback<Result<(), Error>>) -> IndexedDbTask {
+        let error = RefCell::new(Some(error));
+        let notify = move || {
+            if let Some(error) = error.borrow_mut().take() {
+                callback.emit(Err(error));
+            }
+        };
+        let handle = js! {
+            var notify = @{notify};
+            var handle = { active: true, callbacks: [notify] };
+            Promise.resolve().then(function() {
+                if (handle.active) {
+                    handle.active = false;
+                    notify();
+                    notify.drop();
+                }
+            });
+            return handle;
+        };
+        IndexedDbTask(Some(handle))
+    }
+}
+
+impl Task for IndexedDbTask {
+    fn is_active(&self) -> bool {
+        if let Some(ref handle) = self.0 {
+            js! { return @{handle}.active; }.try_into().unwrap_or(false)
+        } else {
+            false
+        }
+    }
+    fn cancel(&mut self) {
+        if let Some(handle) = self.0.take() {
+            js! { @(no_return)
+                var handle = @{handle};
+                if (handle.active) {
+                    handle.active = false;
+                    handle.callbacks.forEach(function(callback) {
+                        callback.drop();
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl Drop for IndexedDbTask {
+    fn drop(&mut self) {
+        if self.is_active() {
+            self.cancel();
+        }
+    }
+}
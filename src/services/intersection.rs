@@ -0,0 +1,145 @@
+//! This module contains the implementation of a service to track
+//! visibility of elements using
+//! [IntersectionObserver](https://developer.mozilla.org/en-US/docs/Web/API/Intersection_Observer_API).
+
+use super::Task;
+use crate::callback::Callback;
+use crate::html::{NodeRef, NodeRefListener};
+use stdweb::Value;
+#[allow(unused_imports)]
+use stdweb::{_js_impl, js};
+
+/// A change of visibility of an observed element.
+#[derive(Debug, Clone)]
+pub struct IntersectionEntry {
+    /// A visible part of the element in interval: `0 <= ratio <= 1`.
+    pub ratio: f64,
+    /// `true` if the element intersects the root.
+    pub is_intersecting: bool,
+}
+
+/// Options of an observer.
+#[derive(Debug, Clone, Default)]
+pub struct IntersectionOptions {
+    /// Margin around the viewport in CSS form, e.g. `"200px 0px"`.
+    /// Use it to load content before it becomes visible.
+    pub root_margin: Option<String>,
+    /// Ratios which fire a callback when crossed. Fires on any
+    /// change of intersection if empty.
+    pub thresholds: Vec<f64>,
+}
+
+/// A handle which stops observing of an element.
+#[must_use]
+pub struct IntersectionTask {
+    handle: Option<Value>,
+    listener: Option<NodeRefListener>,
+}
+
+/// A service to observe visibility of elements.
+#[derive(Default)]
+pub struct IntersectionService {}
+
+impl IntersectionService {
+    /// Creates a new service instance connected to `App` by provided `sender`.
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Observes the element of `node_ref` with default options.
+    pub fn observe(
+        &mut self,
+        node_ref: &NodeRef,
+        callback: Callback<IntersectionEntry>,
+    ) -> IntersectionTask {
+        self.observe_with_options(node_ref, IntersectionOptions::default(), callback)
+    }
+
+    /// Observes the element of `node_ref` and sends every change of its visibility
+    /// to `callback`. If the element is not rendered yet it will be observed
+    /// as soon as it appears. When the element is replaced with a new one,
+    /// the new element is observed instead.
+    pub fn observe_with_options(
+        &mut self,
+        node_ref: &NodeRef,
+        options: IntersectionOptions,
+        callback: Callback<IntersectionEntry>,
+    ) -> IntersectionTask {
+        let callback = move |ratio: f64, is_intersecting: bool| {
+            let entry = IntersectionEntry {
+                ratio,
+                is_intersecting,
+            };
+            callback.emit(entry);
+        };
+        let IntersectionOptions {
+            root_margin,
+            thresholds,
+        } = options;
+        let handle = js! {
+            var callback = @{callback};
+            var options = {};
+            var root_margin = @{root_margin};
+            if (root_margin !== null) {
+                options.rootMargin = root_margin;
+            }
+            var thresholds = @{thresholds};
+            if (thresholds.length > 0) {
+                options.threshold = thresholds;
+            }
+            var observer = new IntersectionObserver(function(entries) {
+                entries.forEach(function(entry) {
+                    callback(entry.intersectionRatio, entry.isIntersecting);
+                });
+            }, options);
+            return {
+                node: null,
+                observer: observer,
+                callback: callback,
+            };
+        };
+        let observed = handle.clone();
+        let listener = node_ref.listen(move |node| {
+            js! { @(no_return)
+                var handle = @{&observed};
+                if (handle.node !== null) {
+                    handle.observer.unobserve(handle.node);
+                }
+                handle.node = @{node};
+                if (handle.node !== null) {
+                    handle.observer.observe(handle.node);
+                }
+            }
+        });
+        IntersectionTask {
+            handle: Some(handle),
+            listener: Some(listener),
+        }
+    }
+}
+
+impl Task for IntersectionTask {
+    fn is_active(&self) -> bool {
+        self.handle.is_some()
+    }
+    fn cancel(&mut self) {
+        let handle = self
+            .handle
+            .take()
+            .expect("tried to cancel intersection twice");
+        self.listener.take();
+        js! { @(no_return)
+            var handle = @{handle};
+            handle.observer.disconnect();
+            handle.callback.drop();
+        }
+    }
+}
+
+impl Drop for IntersectionTask {
+    fn drop(&mut self) {
+        if self.is_active() {
+            self.cancel();
+        }
+    }
+}
@@ -3,6 +3,16 @@
 
 use super::{to_ms, Task};
 use crate::callback::Callback;
+#[cfg(feature = "futures")]
+use futures_core::Stream;
+#[cfg(feature = "futures")]
+use std::cell::RefCell;
+#[cfg(feature = "futures")]
+use std::pin::Pin;
+#[cfg(feature = "futures")]
+use std::rc::Rc;
+#[cfg(feature = "futures")]
+use std::task::{Context, Poll, Waker};
 use std::time::Duration;
 use stdweb::Value;
 #[allow(unused_imports)]
@@ -38,6 +48,8 @@ impl IntervalService {
             var delay = @{ms};
             return {
                 interval_id: setInterval(action, delay),
+                action: action,
+                delay: delay,
                 callback: callback,
             };
         };
@@ -45,6 +57,82 @@ impl IntervalService {
     }
 }
 
+impl IntervalTask {
+    /// Restarts the interval, so the next tick comes after the full duration.
+    pub fn reset(&mut self) {
+        if let Some(ref handle) = self.0 {
+            js! { @(no_return)
+                var handle = @{handle};
+                clearInterval(handle.interval_id);
+                handle.interval_id = setInterval(handle.action, handle.delay);
+            }
+        }
+    }
+}
+
+/// Returns a stream which yields on every elapsed `duration`. Dropping
+/// the stream clears the interval. It's available with the `futures` feature only.
+#[cfg(feature = "futures")]
+pub fn interval(duration: Duration) -> Interval {
+    let state = Rc::new(RefCell::new(IntervalState {
+        ticks: 0,
+        waker: None,
+    }));
+    let on_tick = state.clone();
+    let callback = move |_| {
+        let waker = {
+            let mut state = on_tick.borrow_mut();
+            state.ticks += 1;
+            state.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    };
+    let task = IntervalService::new().spawn(duration, callback.into());
+    Interval { state, task }
+}
+
+#[cfg(feature = "futures")]
+struct IntervalState {
+    ticks: usize,
+    waker: Option<Waker>,
+}
+
+/// A stream of ticks of an interval. Use `interval` to create it.
+/// Ticks which weren't polled in time are yielded later one by one.
+#[cfg(feature = "futures")]
+#[must_use]
+pub struct Interval {
+    state: Rc<RefCell<IntervalState>>,
+    task: IntervalTask,
+}
+
+#[cfg(feature = "futures")]
+impl Interval {
+    /// Restarts the interval and forgets ticks which weren't polled yet.
+    pub fn reset(&mut self) {
+        self.state.borrow_mut().ticks = 0;
+        self.task.reset();
+    }
+}
+
+#[cfg(feature = "futures")]
+impl Stream for Interval {
+    type Item = ();
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut state = self.state.borrow_mut();
+        if state.ticks > 0 {
+            state.ticks -= 1;
+            Poll::Ready(Some(()))
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
 impl Task for IntervalTask {
     fn is_active(&self) -> bool {
         self.0.is_some()
@@ -0,0 +1,326 @@
+//! This module contains a service to register global keyboard shortcuts.
+//!
+//! Shortcuts are written as strings like `"ctrl+shift+k"`, `"mod+s"` or `"?"`,
+//! where `mod` is `cmd` on macOS and `ctrl` on other platforms. Shortcuts
+//! without modifiers or only with `shift` are ignored while a user types
+//! in an input, because they produce text.
+
+use super::Task;
+use crate::callback::Callback;
+use failure::Fail;
+use std::fmt;
+use std::str::FromStr;
+use stdweb::unstable::TryInto;
+use stdweb::web::event::{IKeyboardEvent, KeyDownEvent};
+use stdweb::Value;
+#[allow(unused_imports)]
+use stdweb::{_js_impl, js};
+
+/// A combination of modifiers and a key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    /// `Control` key is held.
+    pub ctrl: bool,
+    /// `Alt` (`Option`) key is held.
+    pub alt: bool,
+    /// `Shift` key is held.
+    pub shift: bool,
+    /// `Meta` (`Command` or `Windows`) key is held.
+    pub meta: bool,
+    /// `mod` is held: `meta` on macOS and `ctrl` on other platforms.
+    pub primary: bool,
+    /// The key in lower case as
+    /// [KeyboardEvent.key](https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key)
+    /// names it, e.g. `k`, `enter` or `arrowup`.
+    pub key: String,
+}
+
+/// Represents errors of parsing of shortcuts.
+#[derive(Debug, Fail, Clone, PartialEq)]
+pub enum ShortcutError {
+    /// A shortcut has no key, only modifiers.
+    #[fail(display = "shortcut has no key")]
+    NoKey,
+    /// A shortcut has more than one key.
+    #[fail(display = "shortcut has more than one key: {}", _0)]
+    ManyKeys(String),
+}
+
+/// A pressed key with held modifiers as a browser reports it in a
+/// [KeyboardEvent](https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyPress {
+    /// A value of `KeyboardEvent.key`, e.g. `k`, `?` or `Enter`.
+    pub key: String,
+    /// A value of `KeyboardEvent.code`, e.g. `KeyK` or `Slash`.
+    pub code: String,
+    /// `Control` key is held.
+    pub ctrl: bool,
+    /// `Alt` (`Option`) key is held.
+    pub alt: bool,
+    /// `Shift` key is held.
+    pub shift: bool,
+    /// `Meta` (`Command` or `Windows`) key is held.
+    pub meta: bool,
+}
+
+impl<'a, E: IKeyboardEvent> From<&'a E> for KeyPress {
+    fn from(event: &'a E) -> Self {
+        KeyPress {
+            key: event.key(),
+            code: event.code(),
+            ctrl: event.ctrl_key(),
+            alt: event.alt_key(),
+            shift: event.shift_key(),
+            meta: event.meta_key(),
+        }
+    }
+}
+
+/// Returns a name of `KeyboardEvent.code` of a key with the `symbol`
+/// on US layouts, because a held `Shift` changes `KeyboardEvent.key` of it.
+fn symbol_code(symbol: &str) -> Option<&'static str> {
+    let code = match symbol {
+        "/" => "slash",
+        "\\" => "backslash",
+        "=" => "equal",
+        "-" => "minus",
+        "," => "comma",
+        "." => "period",
+        ";" => "semicolon",
+        "'" => "quote",
+        "`" => "backquote",
+        "[" => "bracketleft",
+        "]" => "bracketright",
+        _ => return None,
+    };
+    Some(code)
+}
+
+impl Shortcut {
+    /// Returns `true` if the `press` triggers the shortcut. `mod` is
+    /// `meta` if `mac` is `true` and `ctrl` otherwise.
+    ///
+    /// Letters and digits are matched with `code` too, so `shift+1` or
+    /// `alt+k` work when modifiers change `key`. Other symbols are typed
+    /// with `shift` on some layouts (like `?` or `+`), so `shift` is only
+    /// checked if the shortcut has it.
+    pub fn matches(&self, press: &KeyPress, mac: bool) -> bool {
+        let ctrl = self.ctrl || (self.primary && !mac);
+        let meta = self.meta || (self.primary && mac);
+        if press.ctrl != ctrl || press.meta != meta || press.alt != self.alt {
+            return false;
+        }
+        let key = press.key.to_lowercase();
+        let code = press.code.to_lowercase();
+        let mut chars = self.key.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if c.is_ascii_alphanumeric() => {
+                press.shift == self.shift
+                    && (key == self.key
+                        || code == format!("key{}", self.key)
+                        || code == format!("digit{}", self.key))
+            }
+            (Some(c), None) if c.is_ascii_punctuation() => {
+                if self.shift && !press.shift {
+                    return false;
+                }
+                key == self.key
+                    || (self.shift && symbol_code(&self.key).map_or(false, |name| code == name))
+            }
+            _ => press.shift == self.shift && key == self.key,
+        }
+    }
+
+    /// Returns `true` if the shortcut works while a user types in an input.
+    /// Keys without modifiers or only with `shift` (like `shift+k` or `?`)
+    /// produce text, so they are left to the input.
+    pub fn works_in_inputs(&self) -> bool {
+        self.ctrl || self.alt || self.meta || self.primary
+    }
+}
+
+impl FromStr for Shortcut {
+    type Err = ShortcutError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        // A trailing `+` is the key itself, e.g. `ctrl++`
+        let (combo, plus) = if s.ends_with("++") || s == "+" {
+            (s[..s.len() - 1].trim_end_matches('+'), true)
+        } else {
+            (s.as_str(), false)
+        };
+        let mut shortcut = Shortcut {
+            ctrl: false,
+            alt: false,
+            shift: false,
+            meta: false,
+            primary: false,
+            key: String::new(),
+        };
+        let mut key = if plus { Some("+".to_owned()) } else { None };
+        for part in combo
+            .split('+')
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+        {
+            match part {
+                "ctrl" | "control" => shortcut.ctrl = true,
+                "alt" | "option" => shortcut.alt = true,
+                "shift" => shortcut.shift = true,
+                "meta" | "cmd" | "command" | "super" | "win" => shortcut.meta = true,
+                "mod" => shortcut.primary = true,
+                part => {
+                    if key.is_some() {
+                        return Err(ShortcutError::ManyKeys(s.clone()));
+                    }
+                    let part = match part {
+                        "esc" => "escape",
+                        "space" => " ",
+                        "up" => "arrowup",
+                        "down" => "arrowdown",
+                        "left" => "arrowleft",
+                        "right" => "arrowright",
+                        "del" => "delete",
+                        "return" => "enter",
+                        "plus" => "+",
+                        part => part,
+                    };
+                    key = Some(part.to_owned());
+                }
+            }
+        }
+        shortcut.key = key.ok_or(ShortcutError::NoKey)?;
+        Ok(shortcut)
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let modifiers = [
+            (self.primary, "mod"),
+            (self.ctrl, "ctrl"),
+            (self.alt, "alt"),
+            (self.shift, "shift"),
+            (self.meta, "meta"),
+        ];
+        for (_, name) in modifiers.iter().filter(|(held, _)| *held) {
+            write!(f, "{}+", name)?;
+        }
+        match self.key.as_str() {
+            " " => write!(f, "space"),
+            key => write!(f, "{}", key),
+        }
+    }
+}
+
+/// An event of a pressed shortcut.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShortcutEvent {
+    /// The shortcut which was pressed.
+    pub shortcut: Shortcut,
+    /// The key is held down and the event is repeated.
+    pub repeat: bool,
+}
+
+/// A handle of a registered shortcut. The shortcut is removed when the task is dropped.
+#[must_use]
+pub struct ShortcutTask(Option<Value>);
+
+/// A service to register global keyboard shortcuts.
+#[derive(Default)]
+pub struct KeyboardService {}
+
+impl KeyboardService {
+    /// Creates a new service instance connected to `App` by provided `sender`.
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Sends an event to `callback` every time the `shortcut` is pressed
+    /// and prevents a default action of the browser for it.
+    pub fn register(
+        &mut self,
+        shortcut: &str,
+        callback: Callback<ShortcutEvent>,
+    ) -> Result<ShortcutTask, ShortcutError> {
+        let shortcut: Shortcut = shortcut.parse()?;
+        Ok(self.register_shortcut(shortcut, callback))
+    }
+
+    /// The same as `register`, but takes a parsed shortcut.
+    pub fn register_shortcut(
+        &mut self,
+        shortcut: Shortcut,
+        callback: Callback<ShortcutEvent>,
+    ) -> ShortcutTask {
+        let mac: bool = js! {
+            return /Mac|iPhone|iPad|iPod/.test(navigator.platform);
+        }
+        .try_into()
+        .unwrap_or(false);
+        let handler = move |event: KeyDownEvent, editable: bool| -> bool {
+            let press = KeyPress::from(&event);
+            if !shortcut.matches(&press, mac) || (editable && !shortcut.works_in_inputs()) {
+                return false;
+            }
+            callback.emit(ShortcutEvent {
+                shortcut: shortcut.clone(),
+                repeat: event.repeat(),
+            });
+            true
+        };
+        let handle = js! {
+            var handle = { enabled: true, callback: @{handler} };
+            handle.listener = function(event) {
+                if (!handle.enabled) {
+                    return;
+                }
+                var target = event.target;
+                var editable = !!target && (target.isContentEditable
+                    || /^(INPUT|TEXTAREA|SELECT)$/.test(target.tagName));
+                if (handle.callback(event, editable)) {
+                    event.preventDefault();
+                }
+            };
+            document.addEventListener("keydown", handle.listener);
+            return handle;
+        };
+        ShortcutTask(Some(handle))
+    }
+}
+
+impl ShortcutTask {
+    /// Enables or disables the shortcut without removing it. Useful to
+    /// disable shortcuts of a view while a modal is shown.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if let Some(ref handle) = self.0 {
+            js! { @(no_return)
+                @{handle}.enabled = @{enabled};
+            }
+        }
+    }
+}
+
+impl Task for ShortcutTask {
+    fn is_active(&self) -> bool {
+        self.0.is_some()
+    }
+    fn cancel(&mut self) {
+        let handle = self.0.take().expect("tried to cancel shortcut twice");
+        js! { @(no_return)
+            var handle = @{handle};
+            document.removeEventListener("keydown", handle.listener);
+            handle.callback.drop();
+        }
+    }
+}
+
+impl Drop for ShortcutTask {
+    fn drop(&mut self) {
+        if self.is_active() {
+            self.cancel();
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! This module contains the implementation of a service to evaluate
+//! [media queries](https://developer.mozilla.org/en-US/docs/Web/API/Window/matchMedia).
+
+use super::Task;
+use crate::callback::Callback;
+use stdweb::unstable::TryInto;
+use stdweb::Value;
+#[allow(unused_imports)]
+use stdweb::{_js_impl, js};
+
+/// A handle to stop listening of media query changes.
+#[must_use]
+pub struct MediaQueryTask(Option<Value>);
+
+/// A service to evaluate media queries like `(max-width: 600px)`
+/// or `(prefers-color-scheme: dark)`.
+#[derive(Default)]
+pub struct MediaQueryService {}
+
+impl MediaQueryService {
+    /// Creates a new service instance connected to `App` by provided `sender`.
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Returns `true` if the document matches the `query` now.
+    pub fn matches(&self, query: &str) -> bool {
+        js! { return window.matchMedia(@{query}).matches; }
+            .try_into()
+            .unwrap_or(false)
+    }
+
+    /// Sends the result of the `query` to `callback` every time it changes.
+    /// The current result is not sent, use `matches` to get it.
+    pub fn subscribe(&mut self, query: &str, callback: Callback<bool>) -> MediaQueryTask {
+        let callback = move |matches: bool| {
+            callback.emit(matches);
+        };
+        let handle = js! {
+            var callback = @{callback};
+            var list = window.matchMedia(@{query});
+            var listener = function(event) {
+                callback(event.matches);
+            };
+            // `addListener` is the only way to subscribe in old browsers.
+            if (list.addEventListener) {
+                list.addEventListener("change", listener);
+            } else {
+                list.addListener(listener);
+            }
+            return {
+                list: list,
+                listener: listener,
+                callback: callback,
+            };
+        };
+        MediaQueryTask(Some(handle))
+    }
+}
+
+impl Task for MediaQueryTask {
+    fn is_active(&self) -> bool {
+        self.0.is_some()
+    }
+    fn cancel(&mut self) {
+        let handle = self.0.take().expect("tried to cancel media query twice");
+        js! { @(no_return)
+            var handle = @{handle};
+            if (handle.list.removeEventListener) {
+                handle.list.removeEventListener("change", handle.listener);
+            } else {
+                handle.list.removeListener(handle.listener);
+            }
+            handle.callback.drop();
+        }
+    }
+}
+
+impl Drop for MediaQueryTask {
+    fn drop(&mut self) {
+        if self.is_active() {
+            self.cancel();
+        }
+    }
+}
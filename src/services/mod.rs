@@ -5,22 +5,44 @@
 
 pub mod console;
 pub mod dialog;
+pub mod document;
+pub mod drop_zone;
+pub mod eventsource;
 pub mod fetch;
+pub mod geolocation;
+pub mod indexeddb;
+pub mod intersection;
 pub mod interval;
+pub mod keyboard;
+pub mod media_query;
+pub mod notification;
+pub mod profiler;
 pub mod reader;
 pub mod render;
 pub mod storage;
 pub mod timeout;
+pub mod visibility;
 pub mod websocket;
 
 pub use self::console::ConsoleService;
 pub use self::dialog::DialogService;
+pub use self::document::DocumentService;
+pub use self::drop_zone::DropZoneService;
+pub use self::eventsource::EventSourceService;
 pub use self::fetch::FetchService;
+pub use self::geolocation::GeolocationService;
+pub use self::indexeddb::IndexedDbService;
+pub use self::intersection::IntersectionService;
 pub use self::interval::IntervalService;
+pub use self::keyboard::KeyboardService;
+pub use self::media_query::MediaQueryService;
+pub use self::notification::NotificationService;
+pub use self::profiler::ProfilerService;
 pub use self::reader::ReaderService;
 pub use self::render::RenderService;
 pub use self::storage::StorageService;
 pub use self::timeout::TimeoutService;
+pub use self::visibility::VisibilityService;
 pub use self::websocket::WebSocketService;
 
 use std::time::Duration;
@@ -35,7 +57,7 @@ pub trait Task: Drop {
 }
 
 #[doc(hidden)]
-fn to_ms(duration: Duration) -> u32 {
+pub(crate) fn to_ms(duration: Duration) -> u32 {
     let ms = duration.subsec_nanos() / 1_000_000;
     ms + duration.as_secs() as u32 * 1000
 }
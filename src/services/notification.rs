@@ -0,0 +1,189 @@
+//! This module contains the implementation of a service to show
+//! [Notifications](https://developer.mozilla.org/en-US/docs/Web/API/Notifications_API).
+
+use super::Task;
+use crate::callback::Callback;
+use failure::Fail;
+use stdweb::unstable::TryInto;
+use stdweb::Value;
+#[allow(unused_imports)]
+use stdweb::{_js_impl, js};
+
+/// A permission of a page to show notifications.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationPermission {
+    /// A user allowed notifications.
+    Granted,
+    /// A user blocked notifications.
+    Denied,
+    /// A user wasn't asked yet. Notifications are not shown in this state.
+    Default,
+}
+
+impl NotificationPermission {
+    fn from_str(permission: &str) -> Self {
+        match permission {
+            "granted" => NotificationPermission::Granted,
+            "denied" => NotificationPermission::Denied,
+            _ => NotificationPermission::Default,
+        }
+    }
+}
+
+/// Represents errors of showing a notification.
+#[derive(Debug, Fail)]
+pub enum NotificationError {
+    /// The browser doesn't support notifications.
+    #[fail(display = "notifications are not supported")]
+    NotSupported,
+    /// The browser rejected the notification. Contains the message
+    /// of the exception.
+    #[fail(display = "can't show the notification: {}", _0)]
+    Failed(String),
+}
+
+/// Content of a notification.
+#[derive(Debug, Clone, Default)]
+pub struct NotificationOptions {
+    /// A text below the title.
+    pub body: Option<String>,
+    /// An url of an icon.
+    pub icon: Option<String>,
+    /// Notifications with the same tag replace each other.
+    pub tag: Option<String>,
+}
+
+/// A handle of a shown notification. Closes the notification when canceled.
+#[must_use]
+pub struct NotificationTask(Option<Value>);
+
+/// A service to show system notifications.
+#[derive(Default)]
+pub struct NotificationService {}
+
+impl NotificationService {
+    /// Creates a new service instance connected to `App` by provided `sender`.
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Returns `true` if a browser supports notifications.
+    pub fn is_supported(&self) -> bool {
+        js! { return typeof Notification !== "undefined"; }
+            .try_into()
+            .unwrap_or(false)
+    }
+
+    /// Returns the current permission of the page.
+    pub fn permission(&self) -> NotificationPermission {
+        let permission: String = js! {
+            return typeof Notification !== "undefined" ? Notification.permission : "denied";
+        }
+        .try_into()
+        .unwrap_or_default();
+        NotificationPermission::from_str(&permission)
+    }
+
+    /// Asks a user for a permission to show notifications.
+    pub fn request_permission(&mut self, callback: Callback<NotificationPermission>) {
+        let callback = move |permission: String| {
+            callback.emit(NotificationPermission::from_str(&permission));
+        };
+        js! { @(no_return)
+            var callback = @{callback};
+            var done = function(permission) {
+                callback(permission);
+                callback.drop();
+            };
+            if (typeof Notification === "undefined") {
+                done("denied");
+            } else {
+                // Old browsers support callbacks only, new ones could call both.
+                var promise = Notification.requestPermission(function(permission) {
+                    if (!promise) {
+                        done(permission);
+                    }
+                });
+                if (promise) {
+                    promise.then(done);
+                }
+            }
+        }
+    }
+
+    /// Shows a notification. `on_click` is called when a user clicks it.
+    /// Returns an error if notifications are not supported.
+    pub fn show(
+        &mut self,
+        title: &str,
+        options: NotificationOptions,
+        on_click: Callback<()>,
+    ) -> Result<NotificationTask, NotificationError> {
+        if !self.is_supported() {
+            return Err(NotificationError::NotSupported);
+        }
+        let on_click = move || {
+            on_click.emit(());
+        };
+        let NotificationOptions { body, icon, tag } = options;
+        let handle = js! {
+            var on_click = @{on_click};
+            var options = {};
+            var body = @{body};
+            if (body !== null) {
+                options.body = body;
+            }
+            var icon = @{icon};
+            if (icon !== null) {
+                options.icon = icon;
+            }
+            var tag = @{tag};
+            if (tag !== null) {
+                options.tag = tag;
+            }
+            try {
+                var notification = new Notification(@{title}, options);
+            } catch (error) {
+                on_click.drop();
+                return { error: String(error) };
+            }
+            notification.onclick = function() {
+                on_click();
+            };
+            return {
+                notification: notification,
+                on_click: on_click,
+            };
+        };
+        let error: Option<String> = js! { return @{&handle}.error; }
+            .try_into()
+            .unwrap_or_default();
+        match error {
+            Some(error) => Err(NotificationError::Failed(error)),
+            None => Ok(NotificationTask(Some(handle))),
+        }
+    }
+}
+
+impl Task for NotificationTask {
+    fn is_active(&self) -> bool {
+        self.0.is_some()
+    }
+    fn cancel(&mut self) {
+        let handle = self.0.take().expect("tried to cancel notification twice");
+        js! { @(no_return)
+            var handle = @{handle};
+            handle.notification.onclick = null;
+            handle.notification.close();
+            handle.on_click.drop();
+        }
+    }
+}
+
+impl Drop for NotificationTask {
+    fn drop(&mut self) {
+        if self.is_active() {
+            self.cancel();
+        }
+    }
+}
@@ -0,0 +1,198 @@
+//! This module contains a service to profile components.
+//!
+//! When profiling is enabled every `update`, `view` and DOM patch of
+//! a component is timed and marked with
+//! [performance.measure](https://developer.mozilla.org/en-US/docs/Web/API/Performance/measure),
+//! so it's visible in the performance tab of browser's developer tools.
+
+use std::any::type_name;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use stdweb::unstable::TryInto;
+#[allow(unused_imports)]
+use stdweb::{_js_impl, js};
+
+/// A phase of a component's lifecycle which is measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    /// Handling of messages and properties by `update` and `change`.
+    Update,
+    /// Building of a virtual DOM by `view`.
+    View,
+    /// Patching of the DOM with a new virtual DOM.
+    Patch,
+}
+
+impl Phase {
+    fn name(self) -> &'static str {
+        match self {
+            Phase::Update => "update",
+            Phase::View => "view",
+            Phase::Patch => "patch",
+        }
+    }
+}
+
+/// Collected durations of a phase.
+#[derive(Debug, Clone, Default)]
+pub struct Timing {
+    /// How many times the phase was measured.
+    pub count: u32,
+    /// Total duration in milliseconds.
+    pub total_ms: f64,
+    /// The longest duration in milliseconds.
+    pub max_ms: f64,
+}
+
+impl Timing {
+    fn add(&mut self, ms: f64) {
+        self.count += 1;
+        self.total_ms += ms;
+        if ms > self.max_ms {
+            self.max_ms = ms;
+        }
+    }
+}
+
+/// Collected durations of a component type.
+#[derive(Debug, Clone)]
+pub struct ComponentProfile {
+    /// Type name of the component.
+    pub component: &'static str,
+    /// Durations of `update` and `change` calls.
+    pub update: Timing,
+    /// Durations of `view` calls.
+    pub view: Timing,
+    /// Durations of DOM patches.
+    pub patch: Timing,
+}
+
+impl ComponentProfile {
+    fn new(component: &'static str) -> Self {
+        ComponentProfile {
+            component,
+            update: Timing::default(),
+            view: Timing::default(),
+            patch: Timing::default(),
+        }
+    }
+
+    /// Total duration of all phases in milliseconds.
+    pub fn total_ms(&self) -> f64 {
+        self.update.total_ms + self.view.total_ms + self.patch.total_ms
+    }
+}
+
+#[derive(Default)]
+struct Profiler {
+    enabled: bool,
+    profiles: HashMap<&'static str, ComponentProfile>,
+}
+
+thread_local! {
+    static PROFILER: RefCell<Profiler> = RefCell::new(Profiler::default());
+}
+
+fn now() -> f64 {
+    js! { return performance.now(); }.try_into().unwrap_or(0.0)
+}
+
+/// Runs `func` and records its duration for the component if profiling is enabled.
+pub(crate) fn measure<COMP, T, F>(phase: Phase, func: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    let enabled = PROFILER.with(|profiler| profiler.borrow().enabled);
+    if !enabled {
+        return func();
+    }
+    let component = type_name::<COMP>();
+    let start = now();
+    let result = func();
+    let end = now();
+    let label = format!("{} {}", component, phase.name());
+    js! { @(no_return)
+        // Old browsers don't support `measure` with timestamps.
+        try {
+            performance.measure(@{label}, { start: @{start}, end: @{end} });
+        } catch (error) {
+        }
+    }
+    PROFILER.with(|profiler| {
+        let mut profiler = profiler.borrow_mut();
+        let profile = profiler
+            .profiles
+            .entry(component)
+            .or_insert_with(|| ComponentProfile::new(component));
+        let timing = match phase {
+            Phase::Update => &mut profile.update,
+            Phase::View => &mut profile.view,
+            Phase::Patch => &mut profile.patch,
+        };
+        timing.add(end - start);
+    });
+    result
+}
+
+/// A service to profile components of the current thread.
+#[derive(Default)]
+pub struct ProfilerService {}
+
+impl ProfilerService {
+    /// Creates a new service instance connected to `App` by provided `sender`.
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Starts to record durations. Profiling is disabled by default.
+    pub fn enable(&mut self) {
+        PROFILER.with(|profiler| profiler.borrow_mut().enabled = true);
+    }
+
+    /// Stops to record durations. Collected durations are kept.
+    pub fn disable(&mut self) {
+        PROFILER.with(|profiler| profiler.borrow_mut().enabled = false);
+    }
+
+    /// Returns `true` if durations are recorded.
+    pub fn is_enabled(&self) -> bool {
+        PROFILER.with(|profiler| profiler.borrow().enabled)
+    }
+
+    /// Returns collected durations ordered from the slowest component.
+    pub fn report(&self) -> Vec<ComponentProfile> {
+        let mut profiles: Vec<ComponentProfile> =
+            PROFILER.with(|profiler| profiler.borrow().profiles.values().cloned().collect());
+        profiles.sort_by(|a, b| {
+            b.total_ms()
+                .partial_cmp(&a.total_ms())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        profiles
+    }
+
+    /// Removes collected durations.
+    pub fn reset(&mut self) {
+        PROFILER.with(|profiler| profiler.borrow_mut().profiles.clear());
+    }
+
+    /// Prints collected durations to the console as a table.
+    pub fn log_summary(&mut self) {
+        for profile in self.report() {
+            let ComponentProfile {
+                component,
+                update,
+                view,
+                patch,
+            } = profile;
+            js! { @(no_return)
+                console.log(@{component});
+                console.table({
+                    update: { count: @{update.count}, total_ms: @{update.total_ms}, max_ms: @{update.max_ms} },
+                    view: { count: @{view.count}, total_ms: @{view.total_ms}, max_ms: @{view.max_ms} },
+                    patch: { count: @{patch.count}, total_ms: @{patch.total_ms}, max_ms: @{patch.max_ms} },
+                });
+            }
+        }
+    }
+}
@@ -2,7 +2,9 @@
 
 use super::Task;
 use crate::callback::Callback;
+use std::cell::Cell;
 use std::cmp;
+use std::rc::Rc;
 use stdweb::unstable::TryInto;
 use stdweb::web::event::LoadEndEvent;
 pub use stdweb::web::{Blob, File, IBlob};
@@ -19,6 +21,16 @@ pub struct FileData {
     pub content: Vec<u8>,
 }
 
+/// Struct that represents a file encoded as a
+/// [data URL](https://developer.mozilla.org/en-US/docs/Web/HTTP/Basics_of_HTTP/Data_URIs).
+#[derive(Clone, Debug)]
+pub struct FileDataUrl {
+    /// Name of loaded file.
+    pub name: String,
+    /// Content of loaded file as a data URL. Can be used as `src` of images.
+    pub url: String,
+}
+
 /// Struct that represents a chunk of a file.
 #[derive(Clone, Debug)]
 pub enum FileChunk {
@@ -53,22 +65,65 @@ impl ReaderService {
         let file_reader = FileReader::new();
         let reader = file_reader.clone();
         let name = file.name();
-        file_reader.add_event_listener(move |_event: LoadEndEvent| match reader.result() {
-            Some(FileReaderResult::String(_)) => {
-                unreachable!();
+        let cancelled = Rc::new(Cell::new(false));
+        let is_cancelled = cancelled.clone();
+        file_reader.add_event_listener(move |_event: LoadEndEvent| {
+            if is_cancelled.get() {
+                return;
+            }
+            match reader.result() {
+                Some(FileReaderResult::String(_)) => {
+                    unreachable!();
+                }
+                Some(FileReaderResult::ArrayBuffer(buffer)) => {
+                    let array: TypedArray<u8> = buffer.into();
+                    let data = FileData {
+                        name: name.clone(),
+                        content: array.to_vec(),
+                    };
+                    callback.emit(data);
+                }
+                None => {}
+            }
+        });
+        file_reader.read_as_array_buffer(&file).unwrap();
+        ReaderTask {
+            file_reader,
+            cancelled,
+        }
+    }
+
+    /// Reads a file as a data URL and returns it with a callback.
+    pub fn read_file_as_data_url(
+        &mut self,
+        file: File,
+        callback: Callback<FileDataUrl>,
+    ) -> ReaderTask {
+        let file_reader = FileReader::new();
+        let reader = file_reader.clone();
+        let name = file.name();
+        let cancelled = Rc::new(Cell::new(false));
+        let is_cancelled = cancelled.clone();
+        file_reader.add_event_listener(move |_event: LoadEndEvent| {
+            if is_cancelled.get() {
+                return;
             }
-            Some(FileReaderResult::ArrayBuffer(buffer)) => {
-                let array: TypedArray<u8> = buffer.into();
-                let data = FileData {
+            if let Some(FileReaderResult::String(url)) = reader.result() {
+                let data = FileDataUrl {
                     name: name.clone(),
-                    content: array.to_vec(),
+                    url,
                 };
                 callback.emit(data);
             }
-            None => {}
         });
-        file_reader.read_as_array_buffer(&file).unwrap();
-        ReaderTask { file_reader }
+        // TODO Implement `read_as_data_url` method in `stdweb`
+        js! { @(no_return)
+            @{&file_reader}.readAsDataURL(@{&file});
+        }
+        ReaderTask {
+            file_reader,
+            cancelled,
+        }
     }
 
     /// Reads data chunks from a file and returns them with a callback.
@@ -83,7 +138,13 @@ impl ReaderService {
         let mut position = 0;
         let total_size = file.len() as usize;
         let reader = file_reader.clone();
+        let cancelled = Rc::new(Cell::new(false));
+        let is_cancelled = cancelled.clone();
         file_reader.add_event_listener(move |_event: LoadEndEvent| {
+            // Aborted reading fires this event too
+            if is_cancelled.get() {
+                return;
+            }
             match reader.result() {
                 // This branch is used to start reading
                 Some(FileReaderResult::String(_)) => {
@@ -95,7 +156,11 @@ impl ReaderService {
                     let array: TypedArray<u8> = buffer.into();
                     let chunk = FileChunk::DataChunk {
                         data: array.to_vec(),
-                        progress: position as f32 / total_size as f32,
+                        progress: if total_size > 0 {
+                            position as f32 / total_size as f32
+                        } else {
+                            1.0
+                        },
                     };
                     callback.emit(chunk);
                 }
@@ -125,7 +190,10 @@ impl ReaderService {
         .try_into()
         .unwrap();
         file_reader.read_as_text(&blob).unwrap();
-        ReaderTask { file_reader }
+        ReaderTask {
+            file_reader,
+            cancelled,
+        }
     }
 }
 
@@ -133,21 +201,26 @@ impl ReaderService {
 #[must_use]
 pub struct ReaderTask {
     file_reader: FileReader,
+    cancelled: Rc<Cell<bool>>,
 }
 
 impl Task for ReaderTask {
     fn is_active(&self) -> bool {
-        self.file_reader.ready_state() == FileReaderReadyState::Loading
+        !self.cancelled.get() && self.file_reader.ready_state() == FileReaderReadyState::Loading
     }
 
     fn cancel(&mut self) {
-        self.file_reader.abort();
+        self.cancelled.set(true);
+        if self.file_reader.ready_state() == FileReaderReadyState::Loading {
+            self.file_reader.abort();
+        }
     }
 }
 
 impl Drop for ReaderTask {
     fn drop(&mut self) {
-        if self.is_active() {
+        // Stops chunked reading even if it's between chunks.
+        if !self.cancelled.get() {
             self.cancel();
         }
     }
@@ -1,9 +1,14 @@
 //! This module contains the implementation of a service to
 //! use local and session storage of a browser.
 
+use super::Task;
+use crate::callback::Callback;
 use crate::format::Text;
 use failure::Fail;
 use stdweb::web::{window, Storage};
+use stdweb::Value;
+#[allow(unused_imports)]
+use stdweb::{_js_impl, js};
 
 /// Represents errors of a storage.
 #[derive(Debug, Fail)]
@@ -20,6 +25,21 @@ pub enum Area {
     Session,
 }
 
+/// A change of a storage made by another tab or window.
+#[derive(Debug, Clone)]
+pub struct StorageChange {
+    /// A changed key. `None` if the storage was cleared.
+    pub key: Option<String>,
+    /// A value before the change.
+    pub old_value: Option<String>,
+    /// A value after the change. `None` if the key was removed.
+    pub new_value: Option<String>,
+}
+
+/// A handle to stop listening of storage changes.
+#[must_use]
+pub struct StorageTask(Option<Value>);
+
 /// A storage service attached to a context.
 pub struct StorageService {
     storage: Storage,
@@ -65,4 +85,59 @@ impl StorageService {
     pub fn remove(&mut self, key: &str) {
         self.storage.remove(key);
     }
+
+    /// Subscribes to changes of the storage area made by other tabs or
+    /// windows of the same origin. Changes made by the current page
+    /// are not reported by a browser.
+    pub fn subscribe(&mut self, callback: Callback<StorageChange>) -> StorageTask {
+        let callback =
+            move |key: Option<String>, old_value: Option<String>, new_value: Option<String>| {
+                let change = StorageChange {
+                    key,
+                    old_value,
+                    new_value,
+                };
+                callback.emit(change);
+            };
+        let handle = js! {
+            var storage = @{&self.storage};
+            var callback = @{callback};
+            var listener = function(event) {
+                if (event.storageArea === storage) {
+                    callback(event.key, event.oldValue, event.newValue);
+                }
+            };
+            window.addEventListener("storage", listener);
+            return {
+                listener: listener,
+                callback: callback,
+            };
+        };
+        StorageTask(Some(handle))
+    }
+}
+
+impl Task for StorageTask {
+    fn is_active(&self) -> bool {
+        self.0.is_some()
+    }
+    fn cancel(&mut self) {
+        let handle = self
+            .0
+            .take()
+            .expect("tried to cancel storage subscription twice");
+        js! { @(no_return)
+            var handle = @{handle};
+            window.removeEventListener("storage", handle.listener);
+            handle.callback.drop();
+        }
+    }
+}
+
+impl Drop for StorageTask {
+    fn drop(&mut self) {
+        if self.is_active() {
+            self.cancel();
+        }
+    }
 }
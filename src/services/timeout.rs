@@ -3,7 +3,16 @@
 
 use super::{to_ms, Task};
 use crate::callback::Callback;
+use std::cell::RefCell;
+#[cfg(feature = "futures")]
+use std::future::Future;
+#[cfg(feature = "futures")]
+use std::pin::Pin;
+use std::rc::Rc;
+#[cfg(feature = "futures")]
+use std::task::{Context, Poll, Waker};
 use std::time::Duration;
+use stdweb::web::Date;
 use stdweb::Value;
 #[allow(unused_imports)]
 use stdweb::{_js_impl, js};
@@ -32,16 +41,174 @@ impl TimeoutService {
             var callback = @{callback};
             var action = function() {
                 callback();
-                callback.drop();
             };
             var delay = @{ms};
             return {
                 timeout_id: setTimeout(action, delay),
+                action: action,
+                delay: delay,
                 callback: callback,
             };
         };
         TimeoutTask(Some(handle))
     }
+
+    /// Wraps `callback` to emit a value only when `duration` passed
+    /// since the last value. Useful for search-as-you-type inputs.
+    pub fn debounce<IN: 'static>(
+        &mut self,
+        duration: Duration,
+        callback: Callback<IN>,
+    ) -> Callback<IN> {
+        let pending: Rc<RefCell<Option<IN>>> = Rc::new(RefCell::new(None));
+        let timer: RefCell<Option<TimeoutTask>> = RefCell::new(None);
+        let func = move |input| {
+            *pending.borrow_mut() = Some(input);
+            let pending = pending.clone();
+            let callback = callback.clone();
+            let emit = move |_| {
+                let value = pending.borrow_mut().take();
+                if let Some(value) = value {
+                    callback.emit(value);
+                }
+            };
+            // Replacing the task cancels the previous timeout
+            let task = TimeoutService::new().spawn(duration, emit.into());
+            *timer.borrow_mut() = Some(task);
+        };
+        Callback::from(func)
+    }
+
+    /// Wraps `callback` to emit at most one value per `duration`.
+    /// The first value is emitted at once and the last value which came
+    /// during `duration` is emitted when it expires. Useful for scroll handlers.
+    pub fn throttle<IN: 'static>(
+        &mut self,
+        duration: Duration,
+        callback: Callback<IN>,
+    ) -> Callback<IN> {
+        let period = f64::from(to_ms(duration));
+        let state = Rc::new(RefCell::new(Throttle {
+            last: None,
+            pending: None,
+            scheduled: false,
+            timer: None,
+        }));
+        let func = move |input| {
+            let now = Date::now();
+            let mut this = state.borrow_mut();
+            let wait = this.last.map(|last| last + period - now).unwrap_or(0.0);
+            if wait <= 0.0 && !this.scheduled {
+                this.last = Some(now);
+                drop(this);
+                callback.emit(input);
+                return;
+            }
+            this.pending = Some(input);
+            if !this.scheduled {
+                this.scheduled = true;
+                let state = state.clone();
+                let callback = callback.clone();
+                let emit = move |_| {
+                    let value = {
+                        let mut this = state.borrow_mut();
+                        this.scheduled = false;
+                        this.last = Some(Date::now());
+                        this.pending.take()
+                    };
+                    if let Some(value) = value {
+                        callback.emit(value);
+                    }
+                };
+                let wait = Duration::from_millis(wait.max(0.0) as u64);
+                // The finished task is replaced here, not in its own callback
+                this.timer = Some(TimeoutService::new().spawn(wait, emit.into()));
+            }
+        };
+        Callback::from(func)
+    }
+}
+
+struct Throttle<IN> {
+    last: Option<f64>,
+    pending: Option<IN>,
+    scheduled: bool,
+    timer: Option<TimeoutTask>,
+}
+
+impl TimeoutTask {
+    /// Starts to count the duration again. If the timeout has elapsed
+    /// already, the callback will be called once more.
+    pub fn reset(&mut self) {
+        if let Some(ref handle) = self.0 {
+            js! { @(no_return)
+                var handle = @{handle};
+                clearTimeout(handle.timeout_id);
+                handle.timeout_id = setTimeout(handle.action, handle.delay);
+            }
+        }
+    }
+}
+
+/// Returns a future which resolves after `duration`. Dropping the future
+/// clears the timeout. It's available with the `futures` feature only.
+#[cfg(feature = "futures")]
+pub fn sleep(duration: Duration) -> Sleep {
+    let state = Rc::new(RefCell::new(SleepState {
+        elapsed: false,
+        waker: None,
+    }));
+    let on_timeout = state.clone();
+    let callback = move |_| {
+        let waker = {
+            let mut state = on_timeout.borrow_mut();
+            state.elapsed = true;
+            state.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    };
+    let task = TimeoutService::new().spawn(duration, callback.into());
+    Sleep { state, task }
+}
+
+#[cfg(feature = "futures")]
+struct SleepState {
+    elapsed: bool,
+    waker: Option<Waker>,
+}
+
+/// A future which resolves when a timeout elapses. Use `sleep` to create it.
+#[cfg(feature = "futures")]
+#[must_use]
+pub struct Sleep {
+    state: Rc<RefCell<SleepState>>,
+    task: TimeoutTask,
+}
+
+#[cfg(feature = "futures")]
+impl Sleep {
+    /// Starts to count the duration again, even if the future has resolved.
+    pub fn reset(&mut self) {
+        self.state.borrow_mut().elapsed = false;
+        self.task.reset();
+    }
+}
+
+#[cfg(feature = "futures")]
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.borrow_mut();
+        if state.elapsed {
+            Poll::Ready(())
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
 }
 
 impl Task for TimeoutTask {
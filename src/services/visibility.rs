@@ -0,0 +1,90 @@
+//! This module contains the implementation of a service to track
+//! [visibility](https://developer.mozilla.org/en-US/docs/Web/API/Page_Visibility_API)
+//! of the page.
+
+use super::Task;
+use crate::callback::Callback;
+use stdweb::unstable::TryInto;
+use stdweb::Value;
+#[allow(unused_imports)]
+use stdweb::{_js_impl, js};
+
+/// A visibility state of the page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visibility {
+    /// The page is shown in a foreground tab.
+    Visible,
+    /// The page is in a background tab or a window is minimized.
+    Hidden,
+}
+
+/// A handle to stop listening of visibility changes.
+#[must_use]
+pub struct VisibilityTask(Option<Value>);
+
+/// A service to track visibility of the page.
+#[derive(Default)]
+pub struct VisibilityService {}
+
+impl VisibilityService {
+    /// Creates a new service instance connected to `App` by provided `sender`.
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Returns the current visibility of the page.
+    pub fn visibility(&self) -> Visibility {
+        let hidden: bool = js! { return document.hidden; }.try_into().unwrap_or(false);
+        if hidden {
+            Visibility::Hidden
+        } else {
+            Visibility::Visible
+        }
+    }
+
+    /// Sends every change of visibility of the page to `callback`.
+    pub fn subscribe(&mut self, callback: Callback<Visibility>) -> VisibilityTask {
+        let callback = move |hidden: bool| {
+            let visibility = if hidden {
+                Visibility::Hidden
+            } else {
+                Visibility::Visible
+            };
+            callback.emit(visibility);
+        };
+        let handle = js! {
+            var callback = @{callback};
+            var listener = function() {
+                callback(document.hidden);
+            };
+            document.addEventListener("visibilitychange", listener);
+            return {
+                listener: listener,
+                callback: callback,
+            };
+        };
+        VisibilityTask(Some(handle))
+    }
+}
+
+impl Task for VisibilityTask {
+    fn is_active(&self) -> bool {
+        self.0.is_some()
+    }
+    fn cancel(&mut self) {
+        let handle = self.0.take().expect("tried to cancel visibility twice");
+        js! { @(no_return)
+            var handle = @{handle};
+            document.removeEventListener("visibilitychange", handle.listener);
+            handle.callback.drop();
+        }
+    }
+}
+
+impl Drop for VisibilityTask {
+    fn drop(&mut self) {
+        if self.is_active() {
+            self.cancel();
+        }
+    }
+}
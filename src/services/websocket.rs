@@ -1,12 +1,16 @@
 //! Service to connect to a servers by
 //! [`WebSocket` Protocol](https://tools.ietf.org/html/rfc6455).
 
-use super::Task;
+use super::{to_ms, Task};
 use crate::callback::Callback;
 use crate::format::{Binary, Text};
+use failure::Fail;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
 use stdweb::traits::IMessageEvent;
 use stdweb::web::event::{SocketCloseEvent, SocketErrorEvent, SocketMessageEvent, SocketOpenEvent};
-use stdweb::web::{IEventTarget, SocketBinaryType, SocketReadyState, WebSocket};
+use stdweb::web::{set_timeout, IEventTarget, SocketBinaryType, SocketReadyState, WebSocket};
 
 #[derive(Debug)]
 /// A status of a websocket connection. Used for status notification.
@@ -19,11 +23,81 @@ pub enum WebSocketStatus {
     Error,
 }
 
+/// Options of a websocket connection.
+#[derive(Debug, Clone, Default)]
+pub struct WebSocketOptions {
+    /// Reconnects with growing delays when the connection was closed
+    /// not by the task. Every reconnection sends `Opened` status again.
+    pub reconnect: Option<Reconnect>,
+    /// Limit of bytes which are queued by a socket, but not sent yet.
+    /// `try_send` and `try_send_binary` reject data above the limit.
+    pub max_buffered_amount: Option<u64>,
+}
+
+/// Exponential backoff of reconnection. The delay is multiplied by
+/// `factor` after every attempt which failed and it's reset when
+/// the connection is opened.
+#[derive(Debug, Clone)]
+pub struct Reconnect {
+    /// The delay before the first attempt.
+    pub initial: Duration,
+    /// The limit of the delay.
+    pub max: Duration,
+    /// The multiplier of the delay.
+    pub factor: f64,
+}
+
+impl Default for Reconnect {
+    fn default() -> Self {
+        Reconnect {
+            initial: Duration::from_secs(1),
+            max: Duration::from_secs(30),
+            factor: 2.0,
+        }
+    }
+}
+
+impl Reconnect {
+    /// Returns the delay before the attempt with the number (from zero).
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let initial = to_ms(self.initial) as f64;
+        let max = to_ms(self.max) as f64;
+        let delay = initial * self.factor.powi(attempt as i32);
+        Duration::from_millis(delay.min(max) as u64)
+    }
+}
+
+/// Represents errors of sending data with `try_send` methods.
+#[derive(Debug, Fail)]
+pub enum WebSocketSendError {
+    /// The data can't be converted to the format.
+    #[fail(display = "can't convert the data")]
+    Format,
+    /// The connection is not opened (or reconnecting).
+    #[fail(display = "connection is not opened")]
+    NotConnected,
+    /// The socket has queued more than `max_buffered_amount` bytes.
+    #[fail(display = "too much data is buffered by the socket")]
+    Congested,
+    /// The socket failed to send the data.
+    #[fail(display = "can't send the data")]
+    Failed,
+}
+
+struct Connection {
+    url: String,
+    ws: WebSocket,
+    options: WebSocketOptions,
+    attempts: u32,
+    closed: bool,
+    on_message: Rc<dyn Fn(SocketMessageEvent)>,
+    notification: Callback<WebSocketStatus>,
+}
+
 /// A handle to control current websocket connection. Implements `Task` and could be canceled.
 #[must_use]
 pub struct WebSocketTask {
-    ws: WebSocket,
-    notification: Callback<WebSocketStatus>,
+    connection: Rc<RefCell<Connection>>,
 }
 
 /// A websocket service attached to a user context.
@@ -47,21 +121,22 @@ impl WebSocketService {
     where
         OUT: From<Text> + From<Binary>,
     {
-        let ws = WebSocket::new(url).unwrap();
-        ws.set_binary_type(SocketBinaryType::ArrayBuffer);
-        let notify = notification.clone();
-        ws.add_event_listener(move |_: SocketOpenEvent| {
-            notify.emit(WebSocketStatus::Opened);
-        });
-        let notify = notification.clone();
-        ws.add_event_listener(move |_: SocketCloseEvent| {
-            notify.emit(WebSocketStatus::Closed);
-        });
-        let notify = notification.clone();
-        ws.add_event_listener(move |_: SocketErrorEvent| {
-            notify.emit(WebSocketStatus::Error);
-        });
-        ws.add_event_listener(move |event: SocketMessageEvent| {
+        self.connect_with_options(url, callback, notification, WebSocketOptions::default())
+    }
+
+    /// `connect` with provided `WebSocketOptions`. Use it to reconnect
+    /// automatically or to limit buffered data.
+    pub fn connect_with_options<OUT: 'static>(
+        &mut self,
+        url: &str,
+        callback: Callback<OUT>,
+        notification: Callback<WebSocketStatus>,
+        options: WebSocketOptions,
+    ) -> WebSocketTask
+    where
+        OUT: From<Text> + From<Binary>,
+    {
+        let on_message = move |event: SocketMessageEvent| {
             if let Some(bytes) = event.data().into_array_buffer() {
                 let bytes: Vec<u8> = bytes.into();
                 let data = Ok(bytes);
@@ -72,11 +147,96 @@ impl WebSocketService {
                 let out = OUT::from(data);
                 callback.emit(out);
             }
-        });
-        WebSocketTask { ws, notification }
+        };
+        let on_message: Rc<dyn Fn(SocketMessageEvent)> = Rc::new(on_message);
+        let ws = open_socket(url, &on_message, &notification);
+        let connection = Rc::new(RefCell::new(Connection {
+            url: url.to_owned(),
+            ws,
+            options,
+            attempts: 0,
+            closed: false,
+            on_message,
+            notification,
+        }));
+        watch_close(&connection);
+        WebSocketTask { connection }
     }
 }
 
+fn open_socket(
+    url: &str,
+    on_message: &Rc<dyn Fn(SocketMessageEvent)>,
+    notification: &Callback<WebSocketStatus>,
+) -> WebSocket {
+    let ws = WebSocket::new(url).unwrap();
+    ws.set_binary_type(SocketBinaryType::ArrayBuffer);
+    let notify = notification.clone();
+    ws.add_event_listener(move |_: SocketOpenEvent| {
+        notify.emit(WebSocketStatus::Opened);
+    });
+    let notify = notification.clone();
+    ws.add_event_listener(move |_: SocketErrorEvent| {
+        notify.emit(WebSocketStatus::Error);
+    });
+    let on_message = on_message.clone();
+    ws.add_event_listener(move |event: SocketMessageEvent| {
+        on_message(event);
+    });
+    ws
+}
+
+/// Notifies about closing of the current socket and opens a new one
+/// if reconnection is enabled. The backoff is reset when the socket is
+/// opened. Timers keep a weak reference only, so reconnection stops
+/// when the task is dropped.
+fn watch_close(connection: &Rc<RefCell<Connection>>) {
+    let conn = connection.borrow();
+    let weak = Rc::downgrade(connection);
+    conn.ws.add_event_listener(move |_: SocketOpenEvent| {
+        if let Some(connection) = weak.upgrade() {
+            connection.borrow_mut().attempts = 0;
+        }
+    });
+    let weak = Rc::downgrade(connection);
+    let notify = conn.notification.clone();
+    conn.ws.add_event_listener(move |_: SocketCloseEvent| {
+        notify.emit(WebSocketStatus::Closed);
+        let connection = match weak.upgrade() {
+            Some(connection) => connection,
+            None => return,
+        };
+        let delay = {
+            let mut conn = connection.borrow_mut();
+            if conn.closed {
+                return;
+            }
+            let delay = match conn.options.reconnect {
+                Some(ref reconnect) => reconnect.delay(conn.attempts),
+                None => return,
+            };
+            conn.attempts += 1;
+            to_ms(delay)
+        };
+        let weak = Rc::downgrade(&connection);
+        set_timeout(
+            move || {
+                if let Some(connection) = weak.upgrade() {
+                    if connection.borrow().closed {
+                        return;
+                    }
+                    {
+                        let mut conn = connection.borrow_mut();
+                        conn.ws = open_socket(&conn.url, &conn.on_message, &conn.notification);
+                    }
+                    watch_close(&connection);
+                }
+            },
+            delay,
+        );
+    });
+}
+
 impl WebSocketTask {
     /// Sends data to a websocket connection.
     pub fn send<IN>(&mut self, data: IN)
@@ -84,8 +244,9 @@ impl WebSocketTask {
         IN: Into<Text>,
     {
         if let Ok(body) = data.into() {
-            if let Err(_) = self.ws.send_text(&body) {
-                self.notification.emit(WebSocketStatus::Error);
+            let conn = self.connection.borrow();
+            if let Err(_) = conn.ws.send_text(&body) {
+                conn.notification.emit(WebSocketStatus::Error);
             }
         }
     }
@@ -96,25 +257,74 @@ impl WebSocketTask {
         IN: Into<Binary>,
     {
         if let Ok(body) = data.into() {
-            if let Err(_) = self.ws.send_bytes(&body) {
-                self.notification.emit(WebSocketStatus::Error);
+            let conn = self.connection.borrow();
+            if let Err(_) = conn.ws.send_bytes(&body) {
+                conn.notification.emit(WebSocketStatus::Error);
+            }
+        }
+    }
+
+    /// Sends data to a websocket connection if it's opened and not congested.
+    pub fn try_send<IN>(&mut self, data: IN) -> Result<(), WebSocketSendError>
+    where
+        IN: Into<Text>,
+    {
+        let body = data.into().map_err(|_| WebSocketSendError::Format)?;
+        self.check_ready(body.len())?;
+        let conn = self.connection.borrow();
+        conn.ws
+            .send_text(&body)
+            .map_err(|_| WebSocketSendError::Failed)
+    }
+
+    /// Sends binary data to a websocket connection if it's opened and not congested.
+    pub fn try_send_binary<IN>(&mut self, data: IN) -> Result<(), WebSocketSendError>
+    where
+        IN: Into<Binary>,
+    {
+        let body = data.into().map_err(|_| WebSocketSendError::Format)?;
+        self.check_ready(body.len())?;
+        let conn = self.connection.borrow();
+        conn.ws
+            .send_bytes(&body)
+            .map_err(|_| WebSocketSendError::Failed)
+    }
+
+    /// Returns the amount of bytes which are queued, but not sent yet.
+    pub fn buffered_amount(&self) -> u64 {
+        self.connection.borrow().ws.buffered_amount()
+    }
+
+    fn check_ready(&self, len: usize) -> Result<(), WebSocketSendError> {
+        let conn = self.connection.borrow();
+        if conn.ws.ready_state() != SocketReadyState::Open {
+            return Err(WebSocketSendError::NotConnected);
+        }
+        if let Some(max) = conn.options.max_buffered_amount {
+            if conn.ws.buffered_amount() + len as u64 > max {
+                return Err(WebSocketSendError::Congested);
             }
         }
+        Ok(())
     }
 }
 
 impl Task for WebSocketTask {
     fn is_active(&self) -> bool {
-        self.ws.ready_state() == SocketReadyState::Open
+        let conn = self.connection.borrow();
+        !conn.closed && conn.ws.ready_state() == SocketReadyState::Open
     }
     fn cancel(&mut self) {
-        self.ws.close();
+        let mut conn = self.connection.borrow_mut();
+        conn.closed = true;
+        conn.ws.close();
     }
 }
 
 impl Drop for WebSocketTask {
     fn drop(&mut self) {
-        if self.is_active() {
+        // A socket which is still connecting isn't active, but has to be closed too
+        if !self.connection.borrow().closed {
             self.cancel();
         }
     }
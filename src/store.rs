@@ -0,0 +1,110 @@
+//! This module contains a global state store built on agents.
+//!
+//! The state is changed by a `Reducer` only. Every component can dispatch
+//! actions and subscribe to a selected part of the state:
+//!
+//! ```rust,ignore
+//! let callback = link.send_back(Msg::CountChanged);
+//! let mut store = StoreBridge::<Counter>::new(|state| state.count, callback);
+//! store.dispatch(CounterAction::Increment);
+//! ```
+
+use crate::agent::{Agent, AgentLink, Bridge, Bridged, Context, HandlerId, Transferable};
+use crate::callback::Callback;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+
+/// Declares a state of a store and how actions change it.
+pub trait Reducer: 'static {
+    /// The state of a store. A new store starts with the default state.
+    type State: Transferable + Clone + Default;
+    /// Actions which change the state.
+    type Action: Transferable;
+
+    /// Applies the action to the state.
+    fn reduce(state: &mut Self::State, action: Self::Action);
+}
+
+/// An input of `Store` agent.
+#[derive(Serialize, Deserialize, Debug)]
+pub enum StoreInput<A> {
+    /// Applies the action and sends the new state to every subscriber.
+    Dispatch(A),
+    /// Asks for the current state. It's sent back to the requester only.
+    Get,
+}
+
+impl<A> Transferable for StoreInput<A> where A: Serialize + for<'de> Deserialize<'de> {}
+
+/// An agent which keeps the state of a `Reducer` in the current thread.
+/// Every connected bridge gets the state when connected and after every action.
+pub struct Store<R: Reducer> {
+    link: AgentLink<Store<R>>,
+    state: R::State,
+}
+
+impl<R: Reducer> Agent for Store<R> {
+    type Reach = Context;
+    type Message = ();
+    type Input = StoreInput<R::Action>;
+    type Output = R::State;
+
+    fn create(link: AgentLink<Self>) -> Self {
+        Store {
+            link,
+            state: R::State::default(),
+        }
+    }
+
+    fn update(&mut self, _: Self::Message) {}
+
+    fn connected(&mut self, id: HandlerId) {
+        self.link.response(id, self.state.clone());
+    }
+
+    fn handle(&mut self, msg: Self::Input, who: HandlerId) {
+        match msg {
+            StoreInput::Dispatch(action) => {
+                R::reduce(&mut self.state, action);
+                self.link.broadcast(self.state.clone());
+            }
+            StoreInput::Get => {
+                self.link.response(who, self.state.clone());
+            }
+        }
+    }
+}
+
+/// A bridge to `Store` which notifies only when the selected part
+/// of the state changes.
+pub struct StoreBridge<R: Reducer> {
+    bridge: Box<dyn Bridge<Store<R>>>,
+}
+
+impl<R: Reducer> StoreBridge<R> {
+    /// Connects to the store. `selector` takes a part of the state which
+    /// is sent to `callback` on connection and every time it changes.
+    pub fn new<T, F>(selector: F, callback: Callback<T>) -> Self
+    where
+        T: PartialEq + Clone + 'static,
+        F: Fn(&R::State) -> T + 'static,
+    {
+        let last: RefCell<Option<T>> = RefCell::new(None);
+        let on_state = move |state: R::State| {
+            let selected = selector(&state);
+            if last.borrow().as_ref() == Some(&selected) {
+                return;
+            }
+            *last.borrow_mut() = Some(selected.clone());
+            callback.emit(selected);
+        };
+        StoreBridge {
+            bridge: Store::<R>::bridge(on_state.into()),
+        }
+    }
+
+    /// Sends the action to the store.
+    pub fn dispatch(&mut self, action: R::Action) {
+        self.bridge.send(StoreInput::Dispatch(action));
+    }
+}
@@ -0,0 +1,278 @@
+//! This module contains scoped styles of components.
+//!
+//! A style gets a class name generated from its CSS, so styles of
+//! different components never collide. The CSS is injected into a
+//! `<style>` tag once, no matter how many instances use it, and the tag
+//! is removed when the last instance is dropped. Keep a style in the
+//! component instead of creating it in `view`. `&` refers to the generated
+//! class, plain declarations are applied to it and other selectors are
+//! nested in it:
+//!
+//! ```rust,ignore
+//! fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+//!     let style = css!("
+//!         padding: 4px;
+//!         &:hover {{ color: {}; }}
+//!         .icon {{ float: left; }}
+//!     ", props.color);
+//!     Button { style }
+//! }
+//!
+//! fn view(&self) -> Html<Self> {
+//!     html! {
+//!         <button class=self.style.class()>{ "Click" }</button>
+//!     }
+//! }
+//! ```
+//!
+//! Styles with different CSS, e.g. with different colors of instances,
+//! get different classes. Braces of CSS are escaped as `{{` and `}}`
+//! when `css!` takes arguments.
+
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use stdweb::Value;
+#[allow(unused_imports)]
+use stdweb::{_js_impl, js};
+
+thread_local! {
+    static INJECTED: RefCell<HashMap<String, Injected>> = RefCell::new(HashMap::new());
+}
+
+/// A `<style>` tag and the number of styles which use it.
+struct Injected {
+    count: usize,
+    element: Value,
+}
+
+/// A style which is scoped by a generated class name.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Style {
+    class: String,
+}
+
+impl Style {
+    /// Creates a style from `css` and injects it into the document
+    /// if it wasn't injected before.
+    pub fn new(css: &str) -> Self {
+        let mut hasher = DefaultHasher::new();
+        css.hash(&mut hasher);
+        let class = format!("yew-{:x}", hasher.finish());
+        INJECTED.with(|injected| {
+            let mut injected = injected.borrow_mut();
+            let entry = injected.entry(class.clone()).or_insert_with(|| {
+                let css = scope(css, &class);
+                let element = js! {
+                    var style = document.createElement("style");
+                    style.setAttribute("data-yew-style", @{&class});
+                    style.textContent = @{css};
+                    document.head.appendChild(style);
+                    return style;
+                };
+                Injected { count: 0, element }
+            });
+            entry.count += 1;
+        });
+        Style { class }
+    }
+
+    /// Returns the generated class name to use in `class` attribute.
+    pub fn class(&self) -> &str {
+        &self.class
+    }
+}
+
+impl Clone for Style {
+    fn clone(&self) -> Self {
+        INJECTED.with(|injected| {
+            if let Some(entry) = injected.borrow_mut().get_mut(&self.class) {
+                entry.count += 1;
+            }
+        });
+        Style {
+            class: self.class.clone(),
+        }
+    }
+}
+
+impl Drop for Style {
+    fn drop(&mut self) {
+        INJECTED.with(|injected| {
+            let mut injected = injected.borrow_mut();
+            let unused = match injected.get_mut(&self.class) {
+                Some(entry) => {
+                    entry.count -= 1;
+                    entry.count == 0
+                }
+                None => false,
+            };
+            if unused {
+                if let Some(entry) = injected.remove(&self.class) {
+                    js! { @(no_return)
+                        var style = @{entry.element};
+                        if (style.parentNode) {
+                            style.parentNode.removeChild(style);
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Tracks strings, parentheses and brackets of CSS. Characters inside
+/// them are values, e.g. `;` of `url(...)` or `&` of `content: "&"`.
+#[derive(Default)]
+struct Nesting {
+    quote: Option<char>,
+    escaped: bool,
+    depth: usize,
+}
+
+impl Nesting {
+    /// Returns `true` if `ch` is not a part of a string or a group.
+    fn is_plain(&mut self, ch: char) -> bool {
+        if let Some(quote) = self.quote {
+            if self.escaped {
+                self.escaped = false;
+            } else if ch == '\\' {
+                self.escaped = true;
+            } else if ch == quote {
+                self.quote = None;
+            }
+            return false;
+        }
+        match ch {
+            '"' | '\'' => {
+                self.quote = Some(ch);
+                false
+            }
+            '(' | '[' => {
+                self.depth += 1;
+                false
+            }
+            ')' | ']' => {
+                self.depth = self.depth.saturating_sub(1);
+                false
+            }
+            _ => self.depth == 0,
+        }
+    }
+}
+
+/// Scopes `css` by the `class`. Declarations which don't belong to any
+/// rule are applied to the class. Every selector of a rule gets the class
+/// instead of `&` or is nested in the class if it has no `&`. Rules inside
+/// `@media` and `@supports` are scoped too, other at-rules are kept as is.
+pub fn scope(css: &str, class: &str) -> String {
+    scope_block(css, &format!(".{}", class))
+}
+
+fn scope_block(css: &str, selector: &str) -> String {
+    let mut declarations = Vec::new();
+    let mut rules = String::new();
+    let mut nesting = Nesting::default();
+    let mut depth = 0;
+    let mut start = 0;
+    let mut open = 0;
+    for (idx, ch) in css.char_indices() {
+        if !nesting.is_plain(ch) {
+            continue;
+        }
+        match ch {
+            '{' => {
+                if depth == 0 {
+                    open = idx;
+                }
+                depth += 1;
+            }
+            '}' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    let rule = scope_rule(&css[start..open], &css[open + 1..idx], selector);
+                    rules.push_str(&rule);
+                    start = idx + 1;
+                }
+            }
+            ';' if depth == 0 => {
+                declarations.push(css[start..idx].trim());
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    declarations.push(css[start..].trim());
+    declarations.retain(|declaration| !declaration.is_empty());
+    let mut result = String::new();
+    if !declarations.is_empty() {
+        result.push_str(&format!(
+            "{} {{ {}; }}\n",
+            selector,
+            declarations.join("; ")
+        ));
+    }
+    result.push_str(&rules);
+    result
+}
+
+fn scope_rule(prelude: &str, body: &str, selector: &str) -> String {
+    let prelude = prelude.trim();
+    if prelude.starts_with("@media") || prelude.starts_with("@supports") {
+        format!("{} {{\n{}}}\n", prelude, scope_block(body, selector))
+    } else if prelude.starts_with('@') {
+        format!("{} {{ {} }}\n", prelude, body.trim())
+    } else {
+        let selectors: Vec<String> = split_selectors(prelude)
+            .into_iter()
+            .map(|item| scope_selector(item, selector))
+            .collect();
+        format!("{} {{ {} }}\n", selectors.join(", "), body.trim())
+    }
+}
+
+fn split_selectors(prelude: &str) -> Vec<&str> {
+    let mut nesting = Nesting::default();
+    let mut selectors = Vec::new();
+    let mut start = 0;
+    for (idx, ch) in prelude.char_indices() {
+        if nesting.is_plain(ch) && ch == ',' {
+            selectors.push(prelude[start..idx].trim());
+            start = idx + 1;
+        }
+    }
+    selectors.push(prelude[start..].trim());
+    selectors.retain(|item| !item.is_empty());
+    selectors
+}
+
+fn scope_selector(item: &str, selector: &str) -> String {
+    let mut nesting = Nesting::default();
+    let mut result = String::new();
+    let mut has_parent = false;
+    for ch in item.chars() {
+        if nesting.is_plain(ch) && ch == '&' {
+            result.push_str(selector);
+            has_parent = true;
+        } else {
+            result.push(ch);
+        }
+    }
+    if has_parent {
+        result
+    } else {
+        format!("{} {}", selector, item)
+    }
+}
+
+/// Creates a scoped `Style` from CSS. Arguments are formatted like with `format!`.
+#[macro_export]
+macro_rules! css {
+    ($css:expr) => {
+        $crate::style::Style::new($css)
+    };
+    ($css:expr, $($arg:tt)*) => {
+        $crate::style::Style::new(&format!($css, $($arg)*))
+    };
+}
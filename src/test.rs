@@ -0,0 +1,240 @@
+//! This module contains a harness to test components without mounting
+//! them to the DOM.
+//!
+//! A `TestHost` creates a component and renders it every time it has to be
+//! rendered, but the rendered tree is never applied. Tests send messages or
+//! events and check the state of the component or the rendered markup:
+//!
+//! ```rust,ignore
+//! let mut host = TestHost::<Counter>::mount(());
+//! host.send_message(Msg::Increment);
+//! assert_eq!(host.component().value, 1);
+//! assert_eq!(host.html(), "<p>1</p>");
+//! ```
+//!
+//! Nested components are not created by the host, they are rendered
+//! once to a string by `html`.
+//!
+//! Helpers like `click` or `input` simulate user's actions on rendered
+//! elements. The same helpers exist as functions for elements of the DOM,
+//! they are useful for tests of a mounted app.
+
+use crate::html::{Component, ComponentUpdate, Renderable, Scope};
+use crate::virtual_dom::{VNode, VTag};
+use std::cell::Ref;
+use stdweb::web::{document, Element, IElement};
+use stdweb::Value;
+#[allow(unused_imports)]
+use stdweb::{_js_impl, js};
+
+/// A host which drives a component without the DOM.
+pub struct TestHost<COMP: Component + Renderable<COMP>> {
+    scope: Scope<COMP>,
+}
+
+impl<COMP> TestHost<COMP>
+where
+    COMP: Component + Renderable<COMP>,
+{
+    /// Creates the component with `props` and renders it.
+    pub fn mount(props: COMP::Properties) -> Self {
+        let scope = Scope::new().mount_headless(props);
+        TestHost { scope }
+    }
+
+    /// Returns a scope of the component to send messages from callbacks.
+    pub fn scope(&self) -> Scope<COMP> {
+        self.scope.clone()
+    }
+
+    /// Sends a message to the component. It's rendered again if `update` returns `true`.
+    pub fn send_message(&mut self, msg: COMP::Message) {
+        self.scope.send_message(msg);
+    }
+
+    /// Sends a batch of messages to the component.
+    pub fn send_message_batch(&mut self, msgs: Vec<COMP::Message>) {
+        self.scope.send_message_batch(msgs);
+    }
+
+    /// Passes new properties to `change` of the component.
+    pub fn set_props(&mut self, props: COMP::Properties) {
+        self.scope.update(ComponentUpdate::Properties(props));
+    }
+
+    /// Returns the component to check its state.
+    pub fn component(&self) -> Ref<'_, COMP> {
+        self.scope.headless_component()
+    }
+
+    /// Returns the last rendered tree of the component.
+    pub fn view(&self) -> Ref<'_, VNode<COMP>> {
+        self.scope.headless_frame()
+    }
+
+    /// Renders the last rendered tree to an HTML string.
+    pub fn html(&self) -> String {
+        self.view().to_html_string()
+    }
+
+    /// Returns `true` if an element with the `id` is rendered.
+    pub fn contains(&self, id: &str) -> bool {
+        find_by_id(&self.view(), id).is_some()
+    }
+
+    /// Dispatches a JS `event` to listeners of the element with the `id`.
+    /// Events don't bubble, only listeners of that element are called.
+    /// Returns `false` if no listener handled the event.
+    ///
+    /// # Panics
+    ///
+    /// If there is no element with the `id`.
+    pub fn dispatch(&mut self, id: &str, event: Value) -> bool {
+        self.dispatch_with_value(id, event, None)
+    }
+
+    /// Clicks the element with the `id`.
+    pub fn click(&mut self, id: &str) -> bool {
+        self.dispatch(id, click_event())
+    }
+
+    /// Sets `value` of the input with the `id` and fires an `input` event
+    /// as if the value was typed by a user.
+    pub fn input(&mut self, id: &str, value: &str) -> bool {
+        self.dispatch_with_value(id, input_event(), Some(value))
+    }
+
+    /// Sets `value` of the input with the `id` and fires a `change` event.
+    pub fn change(&mut self, id: &str, value: &str) -> bool {
+        self.dispatch_with_value(id, change_event(), Some(value))
+    }
+
+    /// Presses the `key` on the element with the `id`. Keys are named as
+    /// [KeyboardEvent.key](https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key),
+    /// e.g. `"Enter"` or `"a"`.
+    pub fn keydown(&mut self, id: &str, key: &str) -> bool {
+        self.dispatch(id, keyboard_event("keydown", key))
+    }
+
+    fn dispatch_with_value(&mut self, id: &str, event: Value, value: Option<&str>) -> bool {
+        let msgs: Vec<COMP::Message> = {
+            let view = self.view();
+            let vtag = find_by_id(&view, id)
+                .unwrap_or_else(|| panic!("there is no element with id `{}`", id));
+            let element = detached_element(vtag);
+            if let Some(value) = value {
+                set_value(&element, value);
+            }
+            vtag.listeners
+                .iter()
+                .filter_map(|listener| listener.handle(&element, event.clone()))
+                .collect()
+        };
+        let handled = !msgs.is_empty();
+        for msg in msgs {
+            self.scope.send_message(msg);
+        }
+        handled
+    }
+}
+
+impl<COMP: Component + Renderable<COMP>> Drop for TestHost<COMP> {
+    fn drop(&mut self) {
+        self.scope.destroy();
+    }
+}
+
+/// Finds an element with the `id` attribute. Nested components are skipped.
+fn find_by_id<'a, COMP: Component>(node: &'a VNode<COMP>, id: &str) -> Option<&'a VTag<COMP>> {
+    match node {
+        VNode::VTag(vtag) => {
+            if vtag.attributes.get("id").map(String::as_str) == Some(id) {
+                return Some(vtag);
+            }
+            vtag.childs.iter().find_map(|child| find_by_id(child, id))
+        }
+        VNode::VList(vlist) => vlist.childs.iter().find_map(|child| find_by_id(child, id)),
+        VNode::VText(_) | VNode::VComp(_) | VNode::VRef(_) => None,
+    }
+}
+
+/// Creates an element which isn't attached to the document, but has the same
+/// value as the virtual element. Listeners read values of inputs from it.
+fn detached_element<COMP: Component>(vtag: &VTag<COMP>) -> Element {
+    let element = document()
+        .create_element(vtag.tag())
+        .expect("can't create an element for a test");
+    for (name, value) in &vtag.attributes {
+        let _ = element.set_attribute(name, value);
+    }
+    if let Some(ref kind) = vtag.kind {
+        let _ = element.set_attribute("type", kind);
+    }
+    js! { @(no_return)
+        var element = @{&element};
+        var value = @{&vtag.value};
+        if (value !== null) {
+            element.value = value;
+        }
+        element.checked = @{vtag.checked};
+    }
+    element
+}
+
+/// Clicks a mounted element. The event bubbles like a real one.
+pub fn click(element: &Element) {
+    dispatch(element, click_event());
+}
+
+/// Sets `value` of a mounted input and fires an `input` event.
+pub fn input(element: &Element, value: &str) {
+    set_value(element, value);
+    dispatch(element, input_event());
+}
+
+/// Sets `value` of a mounted input and fires a `change` event.
+pub fn change(element: &Element, value: &str) {
+    set_value(element, value);
+    dispatch(element, change_event());
+}
+
+/// Presses the `key` on a mounted element.
+pub fn keydown(element: &Element, key: &str) {
+    dispatch(element, keyboard_event("keydown", key));
+}
+
+fn dispatch(element: &Element, event: Value) {
+    js! { @(no_return)
+        @{element}.dispatchEvent(@{event});
+    }
+}
+
+fn set_value(element: &Element, value: &str) {
+    js! { @(no_return)
+        @{element}.value = @{value};
+    }
+}
+
+fn click_event() -> Value {
+    js! {
+        return new MouseEvent("click", { bubbles: true, cancelable: true, button: 0 });
+    }
+}
+
+fn input_event() -> Value {
+    js! {
+        return new Event("input", { bubbles: true, cancelable: false });
+    }
+}
+
+fn change_event() -> Value {
+    js! {
+        return new Event("change", { bubbles: true, cancelable: false });
+    }
+}
+
+fn keyboard_event(kind: &str, key: &str) -> Value {
+    js! {
+        return new KeyboardEvent(@{kind}, { key: @{key}, bubbles: true, cancelable: true });
+    }
+}
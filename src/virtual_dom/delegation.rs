@@ -0,0 +1,201 @@
+//! This module contains delegation of event listeners.
+//!
+//! Instead of attaching a DOM listener to every element, a single listener
+//! per event type is attached to the element the app is mounted to. It finds
+//! the closest element which has handlers for the event and calls them.
+
+use crate::callback::Callback;
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+use stdweb::unstable::TryInto;
+use stdweb::web::event::{ConcreteEvent, IEvent};
+use stdweb::web::{Element, EventListenerHandle, IEventTarget};
+#[allow(unused_imports)]
+use stdweb::{_js_impl, js};
+
+/// Events which don't bubble and can't be delegated.
+const NON_BUBBLING: &[&str] = &[
+    "blur",
+    "focus",
+    "mouseenter",
+    "mouseleave",
+    "pointerenter",
+    "pointerleave",
+    "scroll",
+];
+
+thread_local! {
+    static NEXT_ID: Cell<u32> = Cell::new(0);
+}
+
+/// Delegated listeners of an app. Scopes of all components of the app share it.
+#[derive(Clone, Default)]
+pub(crate) struct Delegation(Rc<RefCell<Root>>);
+
+#[derive(Default)]
+struct Root {
+    element: Option<Element>,
+    delegated: HashMap<&'static str, EventListenerHandle>,
+    handlers: HashMap<u32, Rc<dyn Any>>,
+}
+
+impl Delegation {
+    /// Sets the element the app is mounted to.
+    pub(crate) fn set_root(&self, element: &Element) {
+        self.0.borrow_mut().element = Some(element.clone());
+    }
+
+    /// Returns the element the app is mounted to.
+    pub(crate) fn root(&self) -> Option<Element> {
+        self.0.borrow().element.clone()
+    }
+
+    /// Removes delegated listeners from the root element. Handlers which
+    /// are still attached to elements don't get events after that.
+    pub(crate) fn detach(&self) {
+        let mut root = self.0.borrow_mut();
+        for (_, handle) in root.delegated.drain() {
+            handle.remove();
+        }
+        root.handlers.clear();
+    }
+
+    /// Attaches a handler of an event to the element. The handler is
+    /// registered for the delegated listener if the event bubbles.
+    pub(crate) fn attach<E, F>(&self, element: &Element, handler: F) -> ListenerHandle
+    where
+        E: ConcreteEvent + Clone + 'static,
+        F: Fn(E) + 'static,
+    {
+        let kind = E::EVENT_TYPE;
+        let root = self.root();
+        let root = match root {
+            Some(ref root) if !NON_BUBBLING.contains(&kind) => root,
+            _ => {
+                let handle = element.add_event_listener(move |event: E| {
+                    event.stop_propagation();
+                    handler(event);
+                });
+                return ListenerHandle(Handle::Direct(handle));
+            }
+        };
+        let id = NEXT_ID.with(|next_id| {
+            let id = next_id.get();
+            next_id.set(id + 1);
+            id
+        });
+        {
+            let mut state = self.0.borrow_mut();
+            if !state.delegated.contains_key(kind) {
+                let this = Rc::downgrade(&self.0);
+                let target = root.clone();
+                let handle =
+                    root.add_event_listener(move |event: E| dispatch(&this, &target, kind, event));
+                state.delegated.insert(kind, handle);
+            }
+            let callback: Callback<E> = handler.into();
+            state.handlers.insert(id, Rc::new(callback));
+        }
+        js! { @(no_return)
+            var element = @{element};
+            var ids = element.__yew_listeners || (element.__yew_listeners = {});
+            (ids[@{kind}] || (ids[@{kind}] = [])).push(@{id});
+        }
+        ListenerHandle(Handle::Delegated {
+            delegation: Rc::downgrade(&self.0),
+            element: element.clone(),
+            kind,
+            id,
+        })
+    }
+}
+
+/// A handle to remove an attached listener.
+#[must_use]
+pub struct ListenerHandle(Handle);
+
+enum Handle {
+    Direct(EventListenerHandle),
+    Delegated {
+        delegation: Weak<RefCell<Root>>,
+        element: Element,
+        kind: &'static str,
+        id: u32,
+    },
+}
+
+impl ListenerHandle {
+    /// Removes the listener from the element.
+    pub fn remove(self) {
+        match self.0 {
+            Handle::Direct(handle) => handle.remove(),
+            Handle::Delegated {
+                delegation,
+                element,
+                kind,
+                id,
+            } => {
+                if let Some(delegation) = delegation.upgrade() {
+                    delegation.borrow_mut().handlers.remove(&id);
+                }
+                js! { @(no_return)
+                    var ids = @{element}.__yew_listeners;
+                    var list = ids && ids[@{kind}];
+                    if (list) {
+                        var index = list.indexOf(@{id});
+                        if (index >= 0) {
+                            list.splice(index, 1);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Calls handlers of the closest element to the target of the event.
+/// Elements of other apps mounted inside of the root are skipped.
+fn dispatch<E>(delegation: &Weak<RefCell<Root>>, root: &Element, kind: &'static str, event: E)
+where
+    E: ConcreteEvent + Clone + 'static,
+{
+    let delegation = match delegation.upgrade() {
+        Some(delegation) => delegation,
+        None => return,
+    };
+    let path: Vec<Vec<u32>> = js! {
+        var root = @{root};
+        var path = [];
+        var node = @{event.as_ref()}.target;
+        while (node && node !== root) {
+            var ids = node.__yew_listeners;
+            if (ids && ids[@{kind}] && ids[@{kind}].length) {
+                path.push(ids[@{kind}].slice());
+            }
+            node = node.parentNode;
+        }
+        return path;
+    }
+    .try_into()
+    .expect("wrong ids of delegated listeners");
+    // Handlers are cloned to release the delegation before calls,
+    // because handlers could attach or remove listeners.
+    let handlers: Vec<Rc<dyn Any>> = {
+        let state = delegation.borrow();
+        path.iter()
+            .map(|ids| {
+                ids.iter()
+                    .filter_map(|id| state.handlers.get(id).cloned())
+                    .collect::<Vec<_>>()
+            })
+            .find(|handlers| !handlers.is_empty())
+            .unwrap_or_default()
+    };
+    for handler in handlers {
+        if let Ok(callback) = handler.downcast::<Callback<E>>() {
+            callback.emit(event.clone());
+        }
+    }
+}
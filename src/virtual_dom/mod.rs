@@ -1,16 +1,23 @@
 //! This module contains the implementation of reactive virtual dom concept.
 
+pub(crate) mod delegation;
 pub mod vcomp;
 pub mod vlist;
 pub mod vnode;
 pub mod vtag;
 pub mod vtext;
 
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::fmt;
-use stdweb::web::{Element, EventListenerHandle, Node};
+use stdweb::unstable::TryInto;
+use stdweb::web::{Element, Node};
+use stdweb::Value;
+#[allow(unused_imports)]
+use stdweb::{_js_impl, js};
 
-pub use self::vcomp::VComp;
+pub use self::delegation::ListenerHandle;
+pub use self::vcomp::{VChild, VComp};
 pub use self::vlist::VList;
 pub use self::vnode::VNode;
 pub use self::vtag::VTag;
@@ -24,7 +31,13 @@ pub trait Listener<COMP: Component> {
     fn kind(&self) -> &'static str;
     /// Attaches listener to the element and uses scope instance to send
     /// prepaired event back to the yew main loop.
-    fn attach(&mut self, element: &Element, scope: Scope<COMP>) -> EventListenerHandle;
+    /// Listeners of bubbling events are delegated to the element the app is mounted to.
+    fn attach(&mut self, element: &Element, scope: Scope<COMP>) -> ListenerHandle;
+    /// Converts an event to a message without attaching to the element.
+    /// Returns `None` if the event has another type or the listener is attached already.
+    fn handle(&self, _element: &Element, _event: Value) -> Option<COMP::Message> {
+        None
+    }
 }
 
 impl<COMP: Component> fmt::Debug for dyn Listener<COMP> {
@@ -36,12 +49,42 @@ impl<COMP: Component> fmt::Debug for dyn Listener<COMP> {
 /// A list of event listeners.
 type Listeners<COMP> = Vec<Box<dyn Listener<COMP>>>;
 
-/// A map of attributes.
-type Attributes = HashMap<String, String>;
+/// A map of attributes. Names are usually static and aren't allocated.
+type Attributes = HashMap<Cow<'static, str>, String>;
 
 /// A set of classes.
 type Classes = HashSet<String>;
 
+/// Writes a text escaped to be a content of an element or a value of an attribute.
+pub(crate) fn write_escaped(buf: &mut String, text: &str) {
+    for ch in text.chars() {
+        match ch {
+            '&' => buf.push_str("&amp;"),
+            '<' => buf.push_str("&lt;"),
+            '>' => buf.push_str("&gt;"),
+            '"' => buf.push_str("&quot;"),
+            '\'' => buf.push_str("&#39;"),
+            ch => buf.push(ch),
+        }
+    }
+}
+
+/// Writes markup of a real DOM node.
+pub(crate) fn write_node(buf: &mut String, node: &Node) {
+    let html: String = js! {
+        var node = @{node};
+        if (node.outerHTML !== undefined) {
+            return node.outerHTML;
+        }
+        var container = document.createElement("div");
+        container.appendChild(node.cloneNode(true));
+        return container.innerHTML;
+    }
+    .try_into()
+    .unwrap_or_default();
+    buf.push_str(&html);
+}
+
 /// Patch for DOM node modification.
 enum Patch<ID, T> {
     Add(ID, T),
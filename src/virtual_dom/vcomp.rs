@@ -1,9 +1,9 @@
 //! This module contains the implementation of a virtual component `VComp`.
 
-use super::{VDiff, VNode};
+use super::{write_node, VDiff, VNode};
 use crate::callback::Callback;
 use crate::html::{Component, ComponentUpdate, NodeCell, Renderable, Scope};
-use std::any::TypeId;
+use std::any::{Any, TypeId};
 use std::cell::RefCell;
 use std::rc::Rc;
 use stdweb::unstable::TryInto;
@@ -19,12 +19,42 @@ type Generator<COMP> = dyn FnOnce(GeneratorType, Scope<COMP>) -> Mounted;
 /// Components can be generated by mounting or by overwriting an old component.
 enum GeneratorType {
     Mount(Element, Node),
-    Overwrite(TypeId, HiddenScope, NodeCell),
+    Overwrite(TypeId, HiddenScope, NodeCell, Box<dyn Any>),
 }
 
 /// A reference to unknown scope which will be attached later with a generator function.
 pub type ScopeHolder<COMP> = Rc<RefCell<Option<Scope<COMP>>>>;
 
+/// A child component which is not converted to `VComp` yet,
+/// so its properties are available to the parent.
+pub struct VChild<CHILD: Component, COMP: Component> {
+    /// Properties of the child component.
+    pub props: CHILD::Properties,
+    scope: ScopeHolder<COMP>,
+}
+
+impl<CHILD, COMP> VChild<CHILD, COMP>
+where
+    CHILD: Component + Renderable<CHILD>,
+    COMP: Component,
+{
+    /// Creates a child component with properties.
+    pub fn new(props: CHILD::Properties, scope: ScopeHolder<COMP>) -> Self {
+        VChild { props, scope }
+    }
+}
+
+impl<CHILD, COMP> From<VChild<CHILD, COMP>> for VNode<COMP>
+where
+    CHILD: Component + Renderable<CHILD>,
+    CHILD::Properties: Clone + PartialEq + 'static,
+    COMP: Component,
+{
+    fn from(vchild: VChild<CHILD, COMP>) -> Self {
+        VNode::VComp(VComp::new::<CHILD>(vchild.props, vchild.scope))
+    }
+}
+
 /// A virtual component.
 pub struct VComp<COMP: Component> {
     type_id: TypeId,
@@ -41,33 +71,51 @@ enum MountState<COMP: Component> {
 
 struct Unmounted<COMP: Component> {
     generator: Box<Generator<COMP>>,
+    render_html: Box<dyn Fn() -> String>,
 }
 
 struct Mounted {
     occupied: NodeCell,
     scope: HiddenScope,
+    props: Box<dyn Any>,
     destroyer: Box<dyn FnOnce()>,
 }
 
 impl<COMP: Component> VComp<COMP> {
     /// This method prepares a generator to make a new instance of the `Component`.
+    /// An instance which is already mounted at the same position gets the
+    /// properties only if they aren't equal to the previous ones.
     pub fn new<CHILD>(props: CHILD::Properties, scope_holder: ScopeHolder<COMP>) -> Self
     where
         CHILD: Component + Renderable<CHILD>,
+        CHILD::Properties: Clone + PartialEq + 'static,
     {
+        // Properties are taken by mounting, rendering to a string uses a copy.
+        let props = Rc::new(RefCell::new(Some(props)));
+        let render_html = {
+            let props = props.clone();
+            move || {
+                let props = props
+                    .borrow()
+                    .clone()
+                    .expect("properties taken by mounting");
+                Scope::<CHILD>::new().render_html(props)
+            }
+        };
         let generator = move |generator_type: GeneratorType, parent: Scope<COMP>| -> Mounted {
-            *scope_holder.borrow_mut() = Some(parent);
+            let props = props.borrow_mut().take().expect("properties taken twice");
+            *scope_holder.borrow_mut() = Some(parent.clone());
             match generator_type {
                 GeneratorType::Mount(element, ancestor) => {
                     let occupied: NodeCell = Rc::new(RefCell::new(None));
-                    let scope: Scope<CHILD> = Scope::new();
+                    let scope: Scope<CHILD> = Scope::child_of(&parent);
 
                     // TODO Consider to send ComponentUpdate::Create after `mount_in_place` call
                     let scope = scope.mount_in_place(
                         element,
                         Some(VNode::VRef(ancestor)),
                         Some(occupied.clone()),
-                        props,
+                        props.clone(),
                     );
 
                     let destroyer = Box::new({
@@ -79,9 +127,10 @@ impl<COMP: Component> VComp<COMP> {
                         occupied,
                         destroyer: Box::new(destroyer),
                         scope: Box::into_raw(Box::new(scope)) as *mut Hidden,
+                        props: Box::new(props),
                     }
                 }
-                GeneratorType::Overwrite(type_id, scope, occupied) => {
+                GeneratorType::Overwrite(type_id, scope, occupied, last_props) => {
                     if type_id != TypeId::of::<CHILD>() {
                         panic!("tried to overwrite a different type of component");
                     }
@@ -91,7 +140,9 @@ impl<COMP: Component> VComp<COMP> {
                         *Box::from_raw(raw)
                     };
 
-                    scope.update(ComponentUpdate::Properties(props));
+                    if last_props.downcast_ref::<CHILD::Properties>() != Some(&props) {
+                        scope.update(ComponentUpdate::Properties(props.clone()));
+                    }
 
                     let destroyer = Box::new({
                         let mut scope = scope.clone();
@@ -102,6 +153,7 @@ impl<COMP: Component> VComp<COMP> {
                         occupied,
                         destroyer: Box::new(destroyer),
                         scope: Box::into_raw(Box::new(scope)) as *mut Hidden,
+                        props: Box::new(props),
                     }
                 }
             }
@@ -111,9 +163,32 @@ impl<COMP: Component> VComp<COMP> {
             type_id: TypeId::of::<CHILD>(),
             state: Rc::new(RefCell::new(MountState::Unmounted(Unmounted {
                 generator: Box::new(generator),
+                render_html: Box::new(render_html),
             }))),
         }
     }
+
+    /// Creates a temporary component from a copy of the properties and
+    /// renders its view to an HTML string, the node still can be mounted.
+    /// The component is destroyed after rendering, but `create` is called,
+    /// so avoid side effects there. A mounted component renders its DOM node.
+    pub fn to_html_string(&self) -> String {
+        let mut buf = String::new();
+        self.write_html(&mut buf);
+        buf
+    }
+
+    pub(crate) fn write_html(&self, buf: &mut String) {
+        match *self.state.borrow() {
+            MountState::Unmounted(ref this) => buf.push_str(&(this.render_html)()),
+            MountState::Mounted(ref mounted) => {
+                if let Some(ref node) = *mounted.occupied.borrow() {
+                    write_node(buf, node);
+                }
+            }
+            _ => {}
+        }
+    }
 }
 
 /// Converts property and attach empty scope holder which will be activated later.
@@ -150,6 +225,15 @@ where
     }
 }
 
+impl<'a, COMP> Transformer<COMP, &'a str, Option<String>> for VComp<COMP>
+where
+    COMP: Component,
+{
+    fn transform(_: ScopeHolder<COMP>, from: &'a str) -> Option<String> {
+        Some(from.to_owned())
+    }
+}
+
 impl<'a, COMP, F, IN> Transformer<COMP, F, Callback<IN>> for VComp<COMP>
 where
     COMP: Component + Renderable<COMP>,
@@ -188,7 +272,7 @@ impl<COMP: Component> Unmounted<COMP> {
     /// Overwrite an existing virtual component with a generator.
     fn replace(self, type_id: TypeId, old: Mounted, env: Scope<COMP>) -> Mounted {
         (self.generator)(
-            GeneratorType::Overwrite(type_id, old.scope, old.occupied),
+            GeneratorType::Overwrite(type_id, old.scope, old.occupied, old.props),
             env,
         )
     }
@@ -209,14 +293,17 @@ where
     fn detach(&mut self, parent: &Node) -> Option<Node> {
         match self.state.replace(MountState::Detached) {
             MountState::Mounted(this) => {
+                let node = this.occupied.borrow_mut().take();
+                let sibling = node.as_ref().and_then(|node| node.next_sibling());
                 (this.destroyer)();
-                this.occupied.borrow_mut().take().and_then(|node| {
-                    let sibling = node.next_sibling();
-                    parent
-                        .remove_child(&node)
-                        .expect("can't remove the component");
-                    sibling
-                })
+                // The component removes its nodes itself unless
+                // it's processing now and will be destroyed later.
+                if let Some(node) = node {
+                    if node.parent_node().is_some() {
+                        let _ = parent.remove_child(&node);
+                    }
+                }
+                sibling
             }
             _ => None,
         }
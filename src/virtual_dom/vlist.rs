@@ -19,6 +19,19 @@ impl<COMP: Component> VList<COMP> {
     pub fn add_child(&mut self, child: VNode<COMP>) {
         self.childs.push(child);
     }
+
+    /// Renders children of the fragment to an HTML string.
+    pub fn to_html_string(&self) -> String {
+        let mut buf = String::new();
+        self.write_html(&mut buf);
+        buf
+    }
+
+    pub(crate) fn write_html(&self, buf: &mut String) {
+        for child in &self.childs {
+            child.write_html(buf);
+        }
+    }
 }
 
 impl<COMP: Component> VDiff for VList<COMP> {
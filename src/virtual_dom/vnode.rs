@@ -1,6 +1,6 @@
 //! This module contains the implementation of abstract virtual node.
 
-use super::{VComp, VDiff, VList, VTag, VText};
+use super::{write_node, VComp, VDiff, VList, VTag, VText};
 use crate::html::{Component, Renderable, Scope};
 use std::cmp::PartialEq;
 use std::fmt;
@@ -20,6 +20,35 @@ pub enum VNode<COMP: Component> {
     VRef(Node),
 }
 
+impl<COMP: Component> VNode<COMP> {
+    /// Renders the tree to an HTML string. Nested components are created
+    /// and rendered too, so the result is the same markup as the DOM has
+    /// after mounting. It's useful to check `view` in tests:
+    ///
+    /// ```rust,ignore
+    /// let html: Html<Model> = html! { <p class="info">{ "Hello" }</p> };
+    /// assert_eq!(html.to_html_string(), r#"<p class="info">Hello</p>"#);
+    /// ```
+    ///
+    /// `Display` isn't implemented for nodes, because every `ToString`
+    /// type converts to a text node.
+    pub fn to_html_string(&self) -> String {
+        let mut buf = String::new();
+        self.write_html(&mut buf);
+        buf
+    }
+
+    pub(crate) fn write_html(&self, buf: &mut String) {
+        match *self {
+            VNode::VTag(ref vtag) => vtag.write_html(buf),
+            VNode::VText(ref vtext) => vtext.write_html(buf),
+            VNode::VComp(ref vcomp) => vcomp.write_html(buf),
+            VNode::VList(ref vlist) => vlist.write_html(buf),
+            VNode::VRef(ref node) => write_node(buf, node),
+        }
+    }
+}
+
 impl<COMP: Component> VDiff for VNode<COMP> {
     type Component = COMP;
 
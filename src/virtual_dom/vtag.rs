@@ -1,19 +1,27 @@
 //! This module contains the implementation of a virtual element node `VTag`.
 
-use super::{Attributes, Classes, Listener, Listeners, Patch, Reform, VDiff, VNode};
-use crate::html::{Component, Scope};
+use super::{
+    write_escaped, Attributes, Classes, Listener, ListenerHandle, Listeners, Patch, Reform, VDiff,
+    VNode,
+};
+use crate::html::{Component, NodeRef, Scope};
 use log::warn;
 use std::borrow::Cow;
 use std::cmp::PartialEq;
-use std::collections::HashSet;
 use std::fmt;
 use stdweb::unstable::TryFrom;
 use stdweb::web::html_element::InputElement;
 use stdweb::web::html_element::TextAreaElement;
-use stdweb::web::{document, Element, EventListenerHandle, IElement, INode, Node};
+use stdweb::web::{document, Element, IElement, INode, Node};
 #[allow(unused_imports)]
 use stdweb::{_js_impl, js};
 
+/// Elements which can't have children and a closing tag.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
 /// A type for a virtual
 /// [Element](https://developer.mozilla.org/en-US/docs/Web/API/Element)
 /// representation.
@@ -43,9 +51,11 @@ pub struct VTag<COMP: Component> {
     /// in original HTML it sets `defaultChecked` value of `InputElement`, but for reactive
     /// frameworks it's more useful to control `checked` value of an `InputElement`.
     pub checked: bool,
+    /// A reference which is set to the rendered element.
+    pub node_ref: NodeRef,
     /// _Service field_. Keeps handler for attached listeners
     /// to have an opportunity to drop them later.
-    captured: Vec<EventListenerHandle>,
+    captured: Vec<ListenerHandle>,
 }
 
 impl<COMP: Component> VTag<COMP> {
@@ -64,6 +74,7 @@ impl<COMP: Component> VTag<COMP> {
             // In HTML node `checked` attribute sets `defaultChecked` parameter,
             // but we use own field to control real `checked` parameter
             checked: false,
+            node_ref: NodeRef::default(),
         }
     }
 
@@ -72,6 +83,67 @@ impl<COMP: Component> VTag<COMP> {
         &self.tag
     }
 
+    /// Renders the element to an HTML string. Attributes and classes
+    /// are sorted by name to get the same string on every call.
+    pub fn to_html_string(&self) -> String {
+        let mut buf = String::new();
+        self.write_html(&mut buf);
+        buf
+    }
+
+    pub(crate) fn write_html(&self, buf: &mut String) {
+        let tag = self.tag.to_lowercase();
+        buf.push('<');
+        buf.push_str(&tag);
+
+        let mut attributes: Vec<(&str, &str)> = self
+            .attributes
+            .iter()
+            .map(|(name, value)| (name.as_ref(), value.as_str()))
+            .collect();
+        let mut classes: Vec<&str> = self.classes.iter().map(String::as_str).collect();
+        classes.sort();
+        let classes = classes.join(" ");
+        if !classes.is_empty() {
+            attributes.push(("class", classes.as_str()));
+        }
+        if let Some(ref kind) = self.kind {
+            attributes.push(("type", kind.as_str()));
+        }
+        if tag != "textarea" {
+            if let Some(ref value) = self.value {
+                attributes.push(("value", value.as_str()));
+            }
+        }
+        if self.checked {
+            attributes.push(("checked", ""));
+        }
+        attributes.sort();
+        for (name, value) in attributes {
+            buf.push(' ');
+            buf.push_str(name);
+            buf.push_str("=\"");
+            write_escaped(buf, value);
+            buf.push('"');
+        }
+        buf.push('>');
+
+        if VOID_ELEMENTS.contains(&tag.as_str()) {
+            return;
+        }
+        if tag == "textarea" {
+            if let Some(ref value) = self.value {
+                write_escaped(buf, value);
+            }
+        }
+        for child in &self.childs {
+            child.write_html(buf);
+        }
+        buf.push_str("</");
+        buf.push_str(&tag);
+        buf.push('>');
+    }
+
     /// Add `VNode` child.
     pub fn add_child(&mut self, child: VNode<COMP>) {
         self.childs.push(child);
@@ -137,7 +209,15 @@ impl<COMP: Component> VTag<COMP> {
     /// it set as attribute. We use workarounds for:
     /// `class`, `type/kind`, `value` and `checked`.
     pub fn add_attribute<T: ToString>(&mut self, name: &str, value: &T) {
-        self.attributes.insert(name.to_owned(), value.to_string());
+        self.attributes
+            .insert(Cow::Owned(name.to_owned()), value.to_string());
+    }
+
+    /// Adds attribute with a static name like `add_attribute`,
+    /// but doesn't allocate the name. `html!` uses it for all attributes.
+    pub fn add_static_attribute<T: ToString>(&mut self, name: &'static str, value: &T) {
+        self.attributes
+            .insert(Cow::Borrowed(name), value.to_string());
     }
 
     /// Adds attributes to a virtual node. Not every attribute works when
@@ -145,7 +225,7 @@ impl<COMP: Component> VTag<COMP> {
     /// `class`, `type/kind`, `value` and `checked`.
     pub fn add_attributes(&mut self, attrs: Vec<(String, String)>) {
         for (name, value) in attrs {
-            self.attributes.insert(name, value);
+            self.attributes.insert(name.into(), value);
         }
     }
 
@@ -173,26 +253,26 @@ impl<COMP: Component> VTag<COMP> {
     /// - items that are the same stay the same.
     ///
     /// Otherwise just add everything.
-    fn diff_classes(&mut self, ancestor: &mut Option<Self>) -> Vec<Patch<String, ()>> {
+    fn diff_classes<'a>(&'a self, ancestor: &'a Option<Self>) -> Vec<Patch<&'a str, ()>> {
         let mut changes = Vec::new();
-        if let &mut Some(ref ancestor) = ancestor {
+        if let Some(ancestor) = ancestor {
             // Only change what is necessary.
             let to_add = self
                 .classes
                 .difference(&ancestor.classes)
-                .map(|class| Patch::Add(class.to_owned(), ()));
+                .map(|class| Patch::Add(class.as_str(), ()));
             changes.extend(to_add);
             let to_remove = ancestor
                 .classes
                 .difference(&self.classes)
-                .map(|class| Patch::Remove(class.to_owned()));
+                .map(|class| Patch::Remove(class.as_str()));
             changes.extend(to_remove);
         } else {
             // Add everything
             let to_add = self
                 .classes
                 .iter()
-                .map(|class| Patch::Add(class.to_owned(), ()));
+                .map(|class| Patch::Add(class.as_str(), ()));
             changes.extend(to_add);
         }
         changes
@@ -200,42 +280,30 @@ impl<COMP: Component> VTag<COMP> {
 
     /// Similar to diff_classes except for attributes.
     ///
-    /// This also handles patching of attributes when the keys are equal but
-    /// the values are different.
-    fn diff_attributes(&mut self, ancestor: &mut Option<Self>) -> Vec<Patch<String, String>> {
+    /// Attributes are compared by name. An attribute is set when it's new
+    /// or its value had changed and it's removed when it's missing.
+    /// Attributes with equal values (`style` for example) aren't touched.
+    fn diff_attributes<'a>(&'a self, ancestor: &'a Option<Self>) -> Vec<Patch<&'a str, &'a str>> {
         let mut changes = Vec::new();
-        if let &mut Some(ref mut ancestor) = ancestor {
+        if let Some(ancestor) = ancestor {
             // Only change what is necessary.
-            let self_keys = self.attributes.keys().collect::<HashSet<_>>();
-            let ancestor_keys = ancestor.attributes.keys().collect::<HashSet<_>>();
-            let to_add = self_keys.difference(&ancestor_keys).map(|key| {
-                let value = self.attributes.get(*key).expect("attribute of vtag lost");
-                Patch::Add(key.to_string(), value.to_string())
-            });
-            changes.extend(to_add);
-            for key in self_keys.intersection(&ancestor_keys) {
-                let self_value = self
-                    .attributes
-                    .get(*key)
-                    .expect("attribute of self side lost");
-                let ancestor_value = ancestor
-                    .attributes
-                    .get(*key)
-                    .expect("attribute of ancestor side lost");
-                if self_value != ancestor_value {
-                    let mutator = Patch::Replace(key.to_string(), self_value.to_string());
-                    changes.push(mutator);
+            for (key, value) in &self.attributes {
+                match ancestor.attributes.get(key) {
+                    Some(ancestor_value) if ancestor_value == value => {}
+                    Some(_) => changes.push(Patch::Replace(key.as_ref(), value.as_str())),
+                    None => changes.push(Patch::Add(key.as_ref(), value.as_str())),
                 }
             }
-            let to_remove = ancestor_keys
-                .difference(&self_keys)
-                .map(|key| Patch::Remove(key.to_string()));
+            let to_remove = ancestor
+                .attributes
+                .keys()
+                .filter(|key| !self.attributes.contains_key(*key))
+                .map(|key| Patch::Remove(key.as_ref()));
             changes.extend(to_remove);
         } else {
             // Add everything
             for (key, value) in &self.attributes {
-                let mutator = Patch::Add(key.to_string(), value.to_string());
-                changes.push(mutator);
+                changes.push(Patch::Add(key.as_ref(), value.as_str()));
             }
         }
         changes
@@ -282,14 +350,16 @@ impl<COMP: Component> VTag<COMP> {
     fn apply_diffs(&mut self, element: &Element, ancestor: &mut Option<Self>) {
         // Update parameters
         let changes = self.diff_classes(ancestor);
-        for change in changes {
+        if !changes.is_empty() {
             let list = element.class_list();
-            match change {
-                Patch::Add(class, _) | Patch::Replace(class, _) => {
-                    list.add(&class).expect("can't add a class");
-                }
-                Patch::Remove(class) => {
-                    list.remove(&class).expect("can't remove a class");
+            for change in changes {
+                match change {
+                    Patch::Add(class, _) | Patch::Replace(class, _) => {
+                        list.add(class).expect("can't add a class");
+                    }
+                    Patch::Remove(class) => {
+                        list.remove(class).expect("can't remove a class");
+                    }
                 }
             }
         }
@@ -298,10 +368,10 @@ impl<COMP: Component> VTag<COMP> {
         for change in changes {
             match change {
                 Patch::Add(key, value) | Patch::Replace(key, value) => {
-                    set_attribute(element, &key, &value);
+                    set_attribute(element, key, value);
                 }
                 Patch::Remove(key) => {
-                    remove_attribute(element, &key);
+                    remove_attribute(element, key);
                 }
             }
         }
@@ -369,6 +439,14 @@ impl<COMP: Component> VDiff for VTag<COMP> {
             .reference
             .take()
             .expect("tried to remove not rendered VTag from DOM");
+        self.node_ref.set(None);
+        for handle in self.captured.drain(..) {
+            handle.remove();
+        }
+        // Children are detached to destroy components inside them.
+        for child in self.childs.iter_mut() {
+            child.detach(node.as_node());
+        }
         let sibling = node.next_sibling();
         if parent.remove_child(&node).is_err() {
             warn!("Node not found to remove VTag");
@@ -441,6 +519,7 @@ impl<COMP: Component> VDiff for VTag<COMP> {
         }
 
         let element = self.reference.clone().expect("element expected");
+        self.node_ref.set(Some(element.as_node().to_owned()));
 
         {
             let mut ancestor_childs = {
@@ -459,6 +538,9 @@ impl<COMP: Component> VDiff for VTag<COMP> {
                 for handle in ancestor.captured.drain(..) {
                     handle.remove();
                 }
+                if ancestor.node_ref != self.node_ref {
+                    ancestor.node_ref.set(None);
+                }
             }
 
             for mut listener in self.listeners.drain(..) {
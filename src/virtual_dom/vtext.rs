@@ -1,6 +1,6 @@
 //! This module contains the implementation of a virtual text node `VText`.
 
-use super::{Reform, VDiff, VNode};
+use super::{write_escaped, Reform, VDiff, VNode};
 use crate::html::{Component, Scope};
 use log::warn;
 use std::cmp::PartialEq;
@@ -28,6 +28,17 @@ impl<COMP: Component> VText<COMP> {
             _comp: PhantomData,
         }
     }
+
+    /// Renders the node to an escaped HTML string.
+    pub fn to_html_string(&self) -> String {
+        let mut buf = String::new();
+        self.write_html(&mut buf);
+        buf
+    }
+
+    pub(crate) fn write_html(&self, buf: &mut String) {
+        write_escaped(buf, &self.text);
+    }
 }
 
 impl<COMP: Component> VDiff for VText<COMP> {
@@ -0,0 +1,94 @@
+use stdweb::web::{document, Element, IElement, INode, IParentNode};
+#[cfg(feature = "wasm-bindgen-test")]
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+use yew::app::App;
+use yew::html::onclick;
+use yew::scheduler::{set_render_mode, RenderMode};
+use yew::virtual_dom::VNode;
+use yew::{html, Component, ComponentLink, Html, Renderable, ShouldRender};
+
+#[cfg(feature = "wasm-bindgen-test")]
+wasm_bindgen_test_configure!(run_in_browser);
+
+struct Counter {
+    total: u32,
+}
+
+impl Component for Counter {
+    type Message = u32;
+    type Properties = ();
+
+    fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+        Counter { total: 0 }
+    }
+
+    fn update(&mut self, value: Self::Message) -> ShouldRender {
+        self.total += value;
+        true
+    }
+}
+
+impl Renderable<Counter> for Counter {
+    fn view(&self) -> Html<Self> {
+        let mut button = html! {
+            <button onclick=|_| 1>{ self.total }</button>
+        };
+        if let VNode::VTag(ref mut vtag) = button {
+            vtag.add_listener(Box::new(onclick::Wrapper::from(|_| 10)));
+        }
+        button
+    }
+}
+
+fn button(element: &Element) -> Element {
+    element
+        .query_selector("button")
+        .unwrap()
+        .expect("button is rendered")
+}
+
+#[test]
+fn listeners_are_delegated_to_a_detached_root() {
+    set_render_mode(RenderMode::Immediate);
+    let element = document().create_element("div").unwrap();
+    App::<Counter>::new().mount(element.clone());
+
+    yew::test::click(&button(&element));
+    assert_eq!(button(&element).text_content().unwrap(), "11");
+}
+
+#[test]
+fn destroyed_app_ignores_events() {
+    set_render_mode(RenderMode::Immediate);
+    let element = document().create_element("div").unwrap();
+    let handle = App::<Counter>::new().mount_to_element(element.clone());
+    let first = button(&element);
+    handle.destroy();
+
+    element.append_child(&first);
+    yew::test::click(&first);
+    assert_eq!(first.text_content().unwrap(), "0");
+}
+
+#[test]
+fn apps_are_destroyed_separately() {
+    set_render_mode(RenderMode::Immediate);
+    let body = document().body().unwrap();
+    let page = document().create_element("div").unwrap();
+    for _ in 0..2 {
+        let widget = document().create_element("div").unwrap();
+        widget.class_list().add("counter-widget").unwrap();
+        page.append_child(&widget);
+    }
+    body.append_child(&page);
+    let mut handles = App::<Counter>::mount_all(".counter-widget");
+    assert_eq!(handles.len(), 2);
+
+    let second = handles.pop().unwrap();
+    handles.pop().unwrap().destroy();
+    yew::test::click(&button(second.element()));
+    assert_eq!(button(second.element()).text_content().unwrap(), "11");
+
+    second.destroy();
+    body.remove_child(&page).unwrap();
+}
@@ -0,0 +1,49 @@
+#[cfg(feature = "wasm-bindgen-test")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use yew::callback::{Callback, CallbackOnce};
+
+fn recorder() -> (Rc<RefCell<Vec<u32>>>, Callback<u32>) {
+    let values = Rc::new(RefCell::new(Vec::new()));
+    let callback = {
+        let values = values.clone();
+        Callback::from(move |value| values.borrow_mut().push(value))
+    };
+    (values, callback)
+}
+
+#[test]
+fn filter_reform_skips_none() {
+    let (values, callback) = recorder();
+    let callback = callback.filter_reform(|text: &str| text.parse().ok());
+    callback.emit("1");
+    callback.emit("x");
+    callback.emit("3");
+    assert_eq!(*values.borrow(), vec![1, 3]);
+}
+
+#[test]
+fn once_ignores_later_emits() {
+    let (values, callback) = recorder();
+    let owned = String::from("moved");
+    let once = Callback::once(move |value| {
+        drop(owned);
+        callback.emit(value);
+    });
+    once.emit(1);
+    once.clone().emit(2);
+    assert_eq!(*values.borrow(), vec![1]);
+}
+
+#[test]
+fn callback_once_reforms_and_converts() {
+    let (values, callback) = recorder();
+    let once = CallbackOnce::from(move |value| callback.emit(value));
+    let once = once.reform(|value: u32| value * 10);
+    let callback: Callback<u32> = once.into();
+    callback.emit(2);
+    callback.emit(3);
+    assert_eq!(*values.borrow(), vec![20]);
+}
@@ -0,0 +1,13 @@
+use log::LevelFilter;
+#[cfg(feature = "wasm-bindgen-test")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+use yew::services::ConsoleService;
+
+#[test]
+fn set_level_keeps_level_of_other_loggers() {
+    log::set_max_level(LevelFilter::Info);
+    let mut console = ConsoleService::new();
+    console.set_level(LevelFilter::Off);
+    assert_eq!(console.level(), LevelFilter::Off);
+    assert_eq!(log::max_level(), LevelFilter::Info);
+}
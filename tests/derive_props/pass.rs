@@ -64,4 +64,45 @@ mod t4 {
     }
 }
 
+mod t5 {
+    use super::*;
+
+    #[derive(Properties)]
+    pub struct Props<T: Clone = std::vec::IntoIter<u32>>
+    where
+        T: Iterator,
+    {
+        #[props(required)]
+        items: T,
+        selected: Option<T::Item>,
+    }
+
+    fn where_clause_and_defaults_should_work() {
+        Props::<std::vec::IntoIter<u32>>::builder()
+            .items(vec![1, 2].into_iter())
+            .selected(Some(2))
+            .build();
+    }
+}
+
+mod t6 {
+    use super::*;
+
+    #[derive(Properties)]
+    pub struct Props {
+        #[props(required, into)]
+        text: String,
+        #[props(into)]
+        title: String,
+    }
+
+    fn into_props_should_convert() {
+        Props::builder().text("hi").build();
+        Props::builder()
+            .text(String::from("hi"))
+            .title("title")
+            .build();
+    }
+}
+
 fn main() {}
@@ -0,0 +1,35 @@
+use yew::prelude::*;
+use yew::router::Routable;
+
+#[derive(Clone, Debug, PartialEq, Routable)]
+enum Route {
+    #[at("/")]
+    Home,
+    #[at("/users/{id}")]
+    User { id: u32 },
+    #[at("/posts/{slug}/comments/{page}")]
+    Comments(String, usize),
+    #[at("/chat", restore_scroll = false)]
+    Chat,
+}
+
+fn main() {
+    assert_eq!(Route::from_path("/"), Some(Route::Home));
+    assert_eq!(Route::from_path("/users/42"), Some(Route::User { id: 42 }));
+    assert_eq!(Route::from_path("/users/abc"), None);
+    assert_eq!(
+        Route::from_path("/posts/hello/comments/2"),
+        Some(Route::Comments("hello".into(), 2))
+    );
+    assert_eq!(Route::from_path("/unknown"), None);
+
+    assert_eq!(Route::Home.to_path(), "/");
+    assert_eq!(Route::User { id: 42 }.to_path(), "/users/42");
+    assert_eq!(
+        Route::Comments("hello".into(), 2).to_path(),
+        "/posts/hello/comments/2"
+    );
+
+    assert!(Route::User { id: 42 }.restore_scroll());
+    assert!(!Route::Chat.restore_scroll());
+}
@@ -0,0 +1,78 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use stdweb::web::{document, Element, File, IParentNode};
+#[allow(unused_imports)]
+use stdweb::{_js_impl, js};
+#[cfg(feature = "wasm-bindgen-test")]
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+use yew::app::App;
+use yew::callback::Callback;
+use yew::html::NodeRef;
+use yew::scheduler::{set_render_mode, RenderMode};
+use yew::services::DropZoneService;
+use yew::{html, Component, ComponentLink, Html, Renderable, ShouldRender};
+
+#[cfg(feature = "wasm-bindgen-test")]
+wasm_bindgen_test_configure!(run_in_browser);
+
+thread_local! {
+    static ZONE: NodeRef = NodeRef::default();
+}
+
+struct Zone {
+    section: bool,
+}
+
+impl Component for Zone {
+    type Message = bool;
+    type Properties = ();
+
+    fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+        Zone { section: false }
+    }
+
+    fn update(&mut self, section: Self::Message) -> ShouldRender {
+        self.section = section;
+        true
+    }
+}
+
+impl Renderable<Zone> for Zone {
+    fn view(&self) -> Html<Self> {
+        let node_ref = ZONE.with(NodeRef::clone);
+        if self.section {
+            html! { <section ref=node_ref /> }
+        } else {
+            html! { <div ref=node_ref /> }
+        }
+    }
+}
+
+fn drag_files(element: &Element) {
+    js! { @(no_return)
+        var files = new DataTransfer();
+        files.items.add(new File([""], "a.txt"));
+        @{element}.dispatchEvent(new DragEvent("dragenter", { dataTransfer: files }));
+    }
+}
+
+#[test]
+fn replaced_element_is_attached() {
+    set_render_mode(RenderMode::Immediate);
+    let element = document().create_element("div").unwrap();
+    let mut scope = App::<Zone>::new().mount(element.clone());
+    let hovers = Rc::new(RefCell::new(Vec::new()));
+    let on_hover = {
+        let hovers = hovers.clone();
+        Callback::from(move |hover| hovers.borrow_mut().push(hover))
+    };
+    let on_drop = Callback::from(|_: Vec<File>| ());
+    let _task = ZONE.with(|node_ref| DropZoneService::new().attach(node_ref, on_drop, on_hover));
+
+    let old = element.query_selector("div").unwrap().unwrap();
+    scope.send_message(true);
+    drag_files(&old);
+    let section = element.query_selector("section").unwrap().unwrap();
+    drag_files(&section);
+    assert_eq!(*hovers.borrow(), vec![true]);
+}
@@ -0,0 +1,88 @@
+use serde::Deserialize;
+#[cfg(feature = "wasm-bindgen-test")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+use yew::bind;
+use yew::events::InputData;
+use yew::form::{self, Binding, Change, FieldChange, FormError, FormState};
+
+struct Model {
+    form: Form,
+}
+
+struct Form {
+    name: String,
+}
+
+enum Msg {
+    Edit(Change<Model>),
+}
+
+impl Model {
+    fn bind_name(&self) -> Binding<Msg> {
+        bind!(self.form.name, Msg::Edit)
+    }
+}
+
+#[test]
+fn bound_field_changes_model() {
+    let mut model = Model {
+        form: Form {
+            name: "old".to_owned(),
+        },
+    };
+    let binding = model.bind_name();
+    assert_eq!(binding.value(), "old");
+
+    let (_, handler) = binding.into_parts();
+    match handler(InputData {
+        value: "new".to_owned(),
+    }) {
+        Msg::Edit(change) => change.apply(&mut model),
+    }
+    assert_eq!(model.form.name, "new");
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct SignUp {
+    email: String,
+    age: u32,
+    nickname: Option<String>,
+}
+
+#[test]
+fn form_state_validates_and_submits() {
+    let mut form = FormState::<SignUp>::new()
+        .initial("age", 18)
+        .validate("email", form::required);
+    assert!(!form.is_any_dirty());
+
+    assert_eq!(form.submit(), Err(FormError::Invalid));
+    assert_eq!(form.visible_error("email"), Some("this field is required"));
+
+    form.apply(FieldChange {
+        name: "email".to_owned(),
+        value: "user@example.com".to_owned(),
+    });
+    form.set("nickname", "");
+    assert!(form.is_dirty("email"));
+    assert!(!form.is_dirty("age"));
+    assert_eq!(form.error("email"), None);
+    assert_eq!(
+        form.submit(),
+        Ok(SignUp {
+            email: "user@example.com".to_owned(),
+            age: 18,
+            nickname: None,
+        })
+    );
+
+    form.set("age", "many");
+    assert!(match form.submit() {
+        Err(FormError::Deserialize(_)) => true,
+        _ => false,
+    });
+
+    form.reset();
+    assert_eq!(form.value("email"), "");
+    assert!(!form.is_submitted());
+}
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "wasm-bindgen-test")]
 use wasm_bindgen_test::wasm_bindgen_test as test;
-use yew::format::{Binary, Json, Text};
+use yew::format::{Binary, BincodeFormat, Formatted, Json, Text};
 
 #[test]
 fn json_format() {
@@ -17,3 +17,15 @@ fn json_format() {
     let _stored: Text = Json(&data).into();
     let _stored: Binary = Json(&data).into();
 }
+
+#[test]
+fn bincode_format() {
+    #[derive(Serialize, Deserialize)]
+    struct Data {
+        value: u8,
+    }
+
+    let stored: Binary = Formatted::<BincodeFormat, _>::new(&Data { value: 123 }).into();
+    let Formatted(data, _): Formatted<BincodeFormat, Result<Data, _>> = Formatted::from(stored);
+    assert_eq!(data.unwrap().value, 123);
+}
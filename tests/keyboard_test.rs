@@ -0,0 +1,82 @@
+#[cfg(feature = "wasm-bindgen-test")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+use yew::services::keyboard::{KeyPress, Shortcut, ShortcutError};
+
+#[test]
+fn parse_shortcuts() {
+    let shortcut: Shortcut = "Ctrl+Shift+K".parse().unwrap();
+    assert!(shortcut.ctrl && shortcut.shift && !shortcut.alt && !shortcut.meta);
+    assert_eq!(shortcut.key, "k");
+    assert_eq!(shortcut.to_string(), "ctrl+shift+k");
+
+    let shortcut: Shortcut = "mod+esc".parse().unwrap();
+    assert!(shortcut.primary && !shortcut.ctrl && !shortcut.meta);
+    assert_eq!(shortcut.key, "escape");
+
+    let shortcut: Shortcut = "ctrl++".parse().unwrap();
+    assert!(shortcut.ctrl);
+    assert_eq!(shortcut.key, "+");
+
+    assert_eq!("ctrl+shift".parse::<Shortcut>(), Err(ShortcutError::NoKey));
+    assert_eq!(
+        "ctrl+a+b".parse::<Shortcut>(),
+        Err(ShortcutError::ManyKeys("ctrl+a+b".to_owned()))
+    );
+}
+
+#[test]
+fn text_shortcuts_do_not_work_in_inputs() {
+    let works = |shortcut: &str| shortcut.parse::<Shortcut>().unwrap().works_in_inputs();
+    assert!(!works("k"));
+    assert!(!works("shift+k"));
+    assert!(!works("shift+/"));
+    assert!(works("ctrl+k"));
+    assert!(works("ctrl+shift+k"));
+    assert!(works("alt+k"));
+    assert!(works("mod+s"));
+}
+
+fn press(key: &str, code: &str, modifiers: &str) -> KeyPress {
+    KeyPress {
+        key: key.to_owned(),
+        code: code.to_owned(),
+        ctrl: modifiers.contains("ctrl"),
+        alt: modifiers.contains("alt"),
+        shift: modifiers.contains("shift"),
+        meta: modifiers.contains("meta"),
+    }
+}
+
+fn matches(shortcut: &str, press: &KeyPress) -> bool {
+    shortcut.parse::<Shortcut>().unwrap().matches(press, false)
+}
+
+#[test]
+fn match_letters_and_digits() {
+    assert!(matches("ctrl+k", &press("k", "KeyK", "ctrl")));
+    assert!(matches("shift+k", &press("K", "KeyK", "shift")));
+    assert!(!matches("k", &press("K", "KeyK", "shift")));
+    assert!(matches("shift+1", &press("!", "Digit1", "shift")));
+    assert!(matches("alt+k", &press("˚", "KeyK", "alt")));
+    assert!(!matches("ctrl+k", &press("k", "KeyK", "ctrl+alt")));
+}
+
+#[test]
+fn match_shifted_symbols() {
+    assert!(matches("?", &press("?", "Slash", "shift")));
+    assert!(matches("shift+/", &press("?", "Slash", "shift")));
+    assert!(!matches("shift+/", &press("/", "Slash", "")));
+    assert!(!matches("/", &press("?", "Slash", "shift")));
+    assert!(matches("ctrl++", &press("+", "Equal", "ctrl+shift")));
+    assert!(matches("ctrl++", &press("+", "NumpadAdd", "ctrl")));
+}
+
+#[test]
+fn match_named_keys_and_mod() {
+    assert!(matches("esc", &press("Escape", "Escape", "")));
+    assert!(!matches("enter", &press("Enter", "Enter", "shift")));
+    let shortcut: Shortcut = "mod+s".parse().unwrap();
+    assert!(shortcut.matches(&press("s", "KeyS", "meta"), true));
+    assert!(!shortcut.matches(&press("s", "KeyS", "meta"), false));
+    assert!(shortcut.matches(&press("s", "KeyS", "ctrl"), false));
+}
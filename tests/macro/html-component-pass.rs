@@ -3,12 +3,13 @@
 #[macro_use]
 mod helpers;
 
-#[derive(Properties, Default, PartialEq)]
+#[derive(Properties, Default, PartialEq, Clone)]
 pub struct ChildProperties {
     pub string: String,
     #[props(required)]
     pub int: i32,
     pub vec: Vec<i32>,
+    pub optional: Option<String>,
 }
 
 pub struct ChildComponent;
@@ -31,12 +32,70 @@ impl Renderable<ChildComponent> for ChildComponent {
     }
 }
 
+#[derive(Properties, Default, PartialEq, Clone)]
+pub struct ContainerProperties {
+    #[props(required)]
+    pub int: i32,
+    pub children: Children<Container>,
+}
+
+pub struct Container;
+impl Component for Container {
+    type Message = ();
+    type Properties = ContainerProperties;
+
+    fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+        Container
+    }
+
+    fn update(&mut self, _: Self::Message) -> ShouldRender {
+        unimplemented!()
+    }
+}
+
+impl Renderable<Container> for Container {
+    fn view(&self) -> Html<Self> {
+        unimplemented!()
+    }
+}
+
+#[derive(Properties, Default, PartialEq, Clone)]
+pub struct LabelProperties {
+    #[props(required, into)]
+    pub text: String,
+    #[props(into)]
+    pub size: f64,
+}
+
+pub struct Label;
+impl Component for Label {
+    type Message = ();
+    type Properties = LabelProperties;
+
+    fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+        Label
+    }
+
+    fn update(&mut self, _: Self::Message) -> ShouldRender {
+        unimplemented!()
+    }
+}
+
+impl Renderable<Label> for Label {
+    fn view(&self) -> Html<Self> {
+        unimplemented!()
+    }
+}
+
 mod scoped {
     pub use super::ChildComponent;
 }
 
 pass_helper! {
     html! { <ChildComponent int=1 /> };
+    html! { <ChildComponent int=1 optional="text" /> };
+    html! { <Label text="hi" /> };
+    html! { <Label text=String::from("hi") size=2 /> };
 
     // backwards compat
     html! { <ChildComponent: int=1 /> };
@@ -76,10 +135,32 @@ pass_helper! {
         </>
     };
 
+    html! {
+        <>
+            <Container int=1 />
+            <Container int=1></Container>
+            <Container int=1>
+                <div>{ "hello world" }</div>
+            </Container>
+            <Container int=1>
+                <div>{ "hello world" }</div>
+                <ChildComponent int=1 />
+                <scoped::ChildComponent int=2 />
+            </Container>
+            <scoped::ChildComponent int=1></scoped::ChildComponent>
+        </>
+    };
+
     let name_expr = "child";
     html! {
         <ChildComponent int=1 string=name_expr />
     };
+
+    let int = 1;
+    let string = String::from("child");
+    html! {
+        <ChildComponent {int} {string} />
+    };
 }
 
 fn main() {}
@@ -0,0 +1,9 @@
+use yew::prelude::*;
+
+fn compile_fail() {
+    html! { match };
+    html! { match 1 };
+    html! { match 1 { _ => <div> } };
+}
+
+fn main() {}
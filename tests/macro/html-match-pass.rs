@@ -0,0 +1,32 @@
+#[macro_use]
+mod helpers;
+
+#[allow(dead_code)]
+enum State {
+    Loading,
+    Ready(u32),
+    Failed,
+}
+
+pass_helper! {
+    let state = State::Ready(1);
+    html! {
+        match state {
+            State::Loading => <p>{ "Loading" }</p>,
+            State::Ready(value) if value > 0 => <p>{ value }</p>,
+            State::Ready(_) | State::Failed => html! { <p>{ "Nothing" }</p> },
+        }
+    };
+
+    let state = State::Failed;
+    html! {
+        <div>
+            match state {
+                State::Loading => { "Loading" }
+                _ => <></>
+            }
+        </div>
+    };
+}
+
+fn main() {}
@@ -30,6 +30,14 @@ fn compile_fail() {
     html! { <input onclick=|| () /> };
     html! { <input onclick=|a, b| () /> };
     html! { <input onclick=|a: String| () /> };
+
+    html! { <br></br> };
+    html! { <img>Invalid</img> };
+    html! { <div><p></div></p> };
+    html! { <div><img /></img></div> };
+
+    html! { <div bind=::yew::form::Binding::new("", |_| ()) /> };
+    html! { <input bind=::yew::form::Binding::new("", |_| ()) value="" /> };
 }
 
 fn main() {}
@@ -20,10 +20,21 @@ pass_helper! {
             </div>
             <img class=("avatar", "hidden") src="http://pic.com" />
             <img class="avatar hidden", />
+            <br>
+            <hr>
             <button onclick=|e| panic!(e) />
+            <button onclick=|e: ClickEvent| panic!(e) />
             <a href="http://google.com" />
+            <div ref=::yew::html::NodeRef::default()></div>
+            <input bind=::yew::form::Binding::new("text", |_| ()) />
+            <textarea bind=::yew::form::Binding::new(42, |_| ()) />
         </div>
     };
+
+    let id = "shorthand";
+    html! {
+        <div {id}></div>
+    };
 }
 
 fn main() {}
@@ -0,0 +1,8 @@
+use yew::prelude::*;
+
+fn compile_fail() {
+    html! { <div onlick="go"></div> };
+    html! { <div colr="red"></div> };
+}
+
+fn main() {}
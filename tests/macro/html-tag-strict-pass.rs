@@ -0,0 +1,16 @@
+#![recursion_limit = "128"]
+
+#[macro_use]
+mod helpers;
+
+pass_helper! {
+    html! {
+        <div id="main" class="page" title="Page" data-id="1" aria-label="Page">
+            <input type="text" value="" placeholder="Name" aria-required="true" />
+            <a href="/" data-tracking="home" onclick=|_| ()>{ "Home" }</a>
+            <svg viewBox="0 0 10 10"></svg>
+        </div>
+    };
+}
+
+fn main() {}
@@ -16,6 +16,9 @@ fn tests() {
     t.pass("tests/macro/html-list-pass.rs");
     t.compile_fail("tests/macro/html-list-fail.rs");
 
+    t.pass("tests/macro/html-match-pass.rs");
+    t.compile_fail("tests/macro/html-match-fail.rs");
+
     t.pass("tests/macro/html-node-pass.rs");
     t.compile_fail("tests/macro/html-node-fail.rs");
 
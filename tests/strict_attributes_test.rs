@@ -0,0 +1,10 @@
+#[allow(dead_code)]
+#[rustversion::attr(
+    since(1.36),
+    cfg_attr(all(feature = "strict_attributes", not(feature = "web_test")), test)
+)]
+fn tests() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/macro/html-tag-strict-pass.rs");
+    t.compile_fail("tests/macro/html-tag-strict-fail.rs");
+}
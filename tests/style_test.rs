@@ -0,0 +1,61 @@
+#[cfg(feature = "wasm-bindgen-test")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+use yew::style::scope;
+
+#[test]
+fn scope_declarations_and_parent_rules() {
+    let css = scope("padding: 4px; &:hover { color: red; } margin: 0", "c");
+    assert_eq!(
+        css,
+        concat!(
+            ".c { padding: 4px; margin: 0; }\n",
+            ".c:hover { color: red; }\n",
+        )
+    );
+}
+
+#[test]
+fn scope_every_selector_of_rules() {
+    let css = scope(".icon { float: left; } p, & > a { margin: 0; }", "c");
+    assert_eq!(
+        css,
+        concat!(
+            ".c .icon { float: left; }\n",
+            ".c p, .c > a { margin: 0; }\n",
+        )
+    );
+}
+
+#[test]
+fn scope_keeps_strings_and_urls() {
+    let css = scope(
+        r#"background: url(data:image/png;base64,AA==); &::before { content: "&, {"; } [title="&"] { color: red; }"#,
+        "c",
+    );
+    assert_eq!(
+        css,
+        concat!(
+            ".c { background: url(data:image/png;base64,AA==); }\n",
+            ".c::before { content: \"&, {\"; }\n",
+            ".c [title=\"&\"] { color: red; }\n",
+        )
+    );
+}
+
+#[test]
+fn scope_rules_of_media_queries() {
+    let css = scope(
+        "@media (max-width: 600px) { padding: 0; .icon { display: none; } } @font-face { font-family: A; }",
+        "c",
+    );
+    assert_eq!(
+        css,
+        concat!(
+            "@media (max-width: 600px) {\n",
+            ".c { padding: 0; }\n",
+            ".c .icon { display: none; }\n",
+            "}\n",
+            "@font-face { font-family: A; }\n",
+        )
+    );
+}
@@ -0,0 +1,74 @@
+use stdweb::web::{document, IElement, IParentNode};
+#[cfg(feature = "wasm-bindgen-test")]
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+use yew::app::App;
+use yew::components::suspense::{self, Suspense};
+use yew::scheduler::{set_render_mode, RenderMode};
+use yew::{html, Component, ComponentLink, Html, Renderable, ShouldRender};
+
+#[cfg(feature = "wasm-bindgen-test")]
+wasm_bindgen_test_configure!(run_in_browser);
+
+struct Loading;
+
+impl Component for Loading {
+    type Message = ();
+    type Properties = ();
+
+    fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+        Loading
+    }
+
+    fn update(&mut self, _: Self::Message) -> ShouldRender {
+        false
+    }
+}
+
+impl Renderable<Loading> for Loading {
+    fn view(&self) -> Html<Self> {
+        suspense::pending()
+    }
+}
+
+struct Page;
+
+impl Component for Page {
+    type Message = ();
+    type Properties = ();
+
+    fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+        Page
+    }
+
+    fn update(&mut self, _: Self::Message) -> ShouldRender {
+        false
+    }
+}
+
+impl Renderable<Page> for Page {
+    fn view(&self) -> Html<Self> {
+        html! {
+            <Suspense fallback=suspense::fallback(|| html! { <p>{ "Loading..." }</p> })>
+                <span>{ "Partial" }</span>
+                <Loading />
+            </Suspense>
+        }
+    }
+}
+
+#[test]
+fn fallback_is_rendered_while_mounting() {
+    set_render_mode(RenderMode::Immediate);
+    let element = document().create_element("div").unwrap();
+    App::<Page>::new().mount(element.clone());
+
+    let content = element
+        .query_selector("[data-yew-suspense]")
+        .unwrap()
+        .expect("content is rendered");
+    assert_eq!(
+        content.get_attribute("style").as_ref().map(String::as_str),
+        Some("display: none;")
+    );
+    assert!(element.query_selector("p").unwrap().is_some());
+}
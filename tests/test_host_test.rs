@@ -0,0 +1,70 @@
+#[cfg(feature = "wasm-bindgen-test")]
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+use yew::test::TestHost;
+use yew::{html, Component, ComponentLink, Html, Renderable, ShouldRender};
+
+#[cfg(feature = "wasm-bindgen-test")]
+wasm_bindgen_test_configure!(run_in_browser);
+
+struct Counter {
+    value: u32,
+}
+
+enum Msg {
+    Increment,
+    Nothing,
+}
+
+impl Component for Counter {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+        Counter { value: 0 }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Increment => {
+                self.value += 1;
+                true
+            }
+            Msg::Nothing => false,
+        }
+    }
+}
+
+impl Renderable<Counter> for Counter {
+    fn view(&self) -> Html<Self> {
+        html! {
+            <>
+                <p id="value">{ self.value }</p>
+                <button id="increment" onclick=|_| Msg::Increment>{ "+" }</button>
+            </>
+        }
+    }
+}
+
+#[test]
+fn it_renders_after_updates() {
+    let mut host = TestHost::<Counter>::mount(());
+    assert_eq!(
+        host.html(),
+        r#"<p id="value">0</p><button id="increment">+</button>"#
+    );
+
+    host.send_message(Msg::Increment);
+    host.send_message(Msg::Nothing);
+    assert_eq!(host.component().value, 1);
+    assert!(host.contains("value"));
+    assert!(!host.contains("other"));
+}
+
+#[test]
+fn it_handles_simulated_events() {
+    let mut host = TestHost::<Counter>::mount(());
+    assert!(host.click("increment"));
+    assert!(host.click("increment"));
+    assert!(!host.keydown("increment", "Enter"));
+    assert_eq!(host.component().value, 2);
+}
@@ -0,0 +1,71 @@
+#[cfg(feature = "wasm-bindgen-test")]
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+
+#[cfg(feature = "wasm-bindgen-test")]
+wasm_bindgen_test_configure!(run_in_browser);
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+use yew::callback::Callback;
+
+fn recorder() -> (Rc<RefCell<Vec<u32>>>, Callback<u32>) {
+    let values = Rc::new(RefCell::new(Vec::new()));
+    let callback = {
+        let values = values.clone();
+        Callback::from(move |value| values.borrow_mut().push(value))
+    };
+    (values, callback)
+}
+
+#[test]
+fn debounce_waits_for_duration() {
+    let (values, callback) = recorder();
+    let debounced = callback.debounced(Duration::from_secs(1));
+    debounced.emit(1);
+    debounced.emit(2);
+    assert!(values.borrow().is_empty());
+}
+
+#[test]
+fn throttle_emits_first_value_at_once() {
+    let (values, callback) = recorder();
+    let throttled = callback.throttled(Duration::from_secs(1));
+    throttled.emit(1);
+    throttled.emit(2);
+    throttled.emit(3);
+    assert_eq!(*values.borrow(), vec![1]);
+}
+
+#[cfg(all(feature = "futures", feature = "wasm-bindgen-test"))]
+mod deferred {
+    use super::recorder;
+    use std::time::Duration;
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use yew::services::timeout::sleep;
+
+    #[wasm_bindgen_test]
+    async fn debounced_emits_last_value_after_duration() {
+        let (values, callback) = recorder();
+        let debounced = callback.debounced(Duration::from_millis(200));
+        debounced.emit(1);
+        sleep(Duration::from_millis(50)).await;
+        debounced.emit(2);
+        sleep(Duration::from_millis(50)).await;
+        assert!(values.borrow().is_empty());
+        sleep(Duration::from_millis(400)).await;
+        assert_eq!(*values.borrow(), vec![2]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn throttled_emits_last_value_after_duration() {
+        let (values, callback) = recorder();
+        let throttled = callback.throttled(Duration::from_millis(50));
+        throttled.emit(1);
+        throttled.emit(2);
+        throttled.emit(3);
+        assert_eq!(*values.borrow(), vec![1]);
+        sleep(Duration::from_millis(100)).await;
+        assert_eq!(*values.borrow(), vec![1, 3]);
+    }
+}
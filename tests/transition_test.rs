@@ -0,0 +1,63 @@
+use std::time::Duration;
+use stdweb::web::{document, Element, IElement, IParentNode};
+#[cfg(feature = "wasm-bindgen-test")]
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+use yew::app::App;
+use yew::components::Transition;
+use yew::scheduler::{set_render_mode, RenderMode};
+use yew::{html, Component, ComponentLink, Html, Renderable, ShouldRender};
+
+#[cfg(feature = "wasm-bindgen-test")]
+wasm_bindgen_test_configure!(run_in_browser);
+
+struct Modal {
+    show: bool,
+}
+
+impl Component for Modal {
+    type Message = bool;
+    type Properties = ();
+
+    fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+        Modal { show: false }
+    }
+
+    fn update(&mut self, show: Self::Message) -> ShouldRender {
+        self.show = show;
+        true
+    }
+}
+
+impl Renderable<Modal> for Modal {
+    fn view(&self) -> Html<Self> {
+        html! {
+            <Transition name="fade" show=self.show timeout=Duration::from_secs(10)>
+                <span />
+            </Transition>
+        }
+    }
+}
+
+fn wrapper(element: &Element) -> Option<Element> {
+    element.query_selector("div").unwrap()
+}
+
+#[test]
+fn start_classes_are_rendered_before_active_ones() {
+    set_render_mode(RenderMode::Immediate);
+    let element = document().create_element("div").unwrap();
+    let mut scope = App::<Modal>::new().mount(element.clone());
+    assert!(wrapper(&element).is_none());
+
+    scope.send_message(true);
+    let classes = wrapper(&element)
+        .expect("children are rendered")
+        .class_list();
+    assert!(classes.contains("fade-enter"));
+    assert!(!classes.contains("fade-enter-active"));
+
+    scope.send_message(false);
+    let classes = wrapper(&element).expect("children are kept").class_list();
+    assert!(classes.contains("fade-exit"));
+    assert!(!classes.contains("fade-exit-active"));
+}
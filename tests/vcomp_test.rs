@@ -1,6 +1,10 @@
+use std::cell::{Cell, RefCell};
+use stdweb::web::{document, INode};
 #[cfg(feature = "wasm-bindgen-test")]
 use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+use yew::app::App;
 use yew::macros::Properties;
+use yew::scheduler::{set_render_mode, RenderMode};
 use yew::virtual_dom::VNode;
 use yew::{html, Component, ComponentLink, Html, Renderable, ShouldRender};
 
@@ -9,7 +13,7 @@ wasm_bindgen_test_configure!(run_in_browser);
 
 struct Comp;
 
-#[derive(PartialEq, Properties)]
+#[derive(Clone, PartialEq, Properties)]
 struct Props {
     field_1: u32,
     field_2: u32,
@@ -61,3 +65,157 @@ fn set_properties_to_component() {
         <Comp with props />
     };
 }
+
+#[test]
+fn props_are_evaluated_in_source_order() {
+    let order = RefCell::new(Vec::new());
+    let value = |field: u32| {
+        order.borrow_mut().push(field);
+        field
+    };
+
+    let _: VNode<Comp> = html! {
+        <Comp field_2=value(2) field_1=value(1) />
+    };
+
+    assert_eq!(*order.borrow(), vec![2, 1]);
+}
+
+thread_local! {
+    static CREATED: Cell<u32> = Cell::new(0);
+    static CHANGED: Cell<u32> = Cell::new(0);
+}
+
+struct Child;
+
+#[derive(Clone, PartialEq, Properties)]
+struct ChildProps {
+    value: u32,
+}
+
+impl Component for Child {
+    type Message = ();
+    type Properties = ChildProps;
+
+    fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+        CREATED.with(|created| created.set(created.get() + 1));
+        Child
+    }
+
+    fn update(&mut self, _: Self::Message) -> ShouldRender {
+        unimplemented!();
+    }
+
+    fn change(&mut self, _: Self::Properties) -> ShouldRender {
+        CHANGED.with(|changed| changed.set(changed.get() + 1));
+        true
+    }
+}
+
+impl Renderable<Child> for Child {
+    fn view(&self) -> Html<Self> {
+        html! { <span /> }
+    }
+}
+
+struct Parent {
+    value: u32,
+}
+
+impl Component for Parent {
+    type Message = u32;
+    type Properties = ();
+
+    fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+        Parent { value: 0 }
+    }
+
+    fn update(&mut self, value: Self::Message) -> ShouldRender {
+        self.value = value;
+        true
+    }
+}
+
+impl Renderable<Parent> for Parent {
+    fn view(&self) -> Html<Self> {
+        html! {
+            <div>
+                <Child value=self.value />
+            </div>
+        }
+    }
+}
+
+#[test]
+fn child_is_kept_and_gets_only_changed_properties() {
+    set_render_mode(RenderMode::Immediate);
+    let element = document().create_element("div").unwrap();
+    let mut scope = App::<Parent>::new().mount(element);
+
+    scope.send_message(0);
+    assert_eq!(CHANGED.with(Cell::get), 0);
+
+    scope.send_message(1);
+    assert_eq!(CHANGED.with(Cell::get), 1);
+    assert_eq!(CREATED.with(Cell::get), 1);
+}
+
+thread_local! {
+    static RENDERED: RefCell<String> = RefCell::new(String::new());
+}
+
+struct Label;
+
+impl Component for Label {
+    type Message = ();
+    type Properties = ChildProps;
+
+    fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+        Label
+    }
+
+    fn update(&mut self, _: Self::Message) -> ShouldRender {
+        unimplemented!();
+    }
+}
+
+impl Renderable<Label> for Label {
+    fn view(&self) -> Html<Self> {
+        html! { <span>{ "label" }</span> }
+    }
+}
+
+struct Prerendered;
+
+impl Component for Prerendered {
+    type Message = ();
+    type Properties = ();
+
+    fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+        Prerendered
+    }
+
+    fn update(&mut self, _: Self::Message) -> ShouldRender {
+        unimplemented!();
+    }
+}
+
+impl Renderable<Prerendered> for Prerendered {
+    fn view(&self) -> Html<Self> {
+        let label: Html<Self> = html! { <Label value=1 /> };
+        let first = label.to_html_string();
+        assert_eq!(first, label.to_html_string());
+        RENDERED.with(|rendered| *rendered.borrow_mut() = first);
+        html! { <div>{ label }</div> }
+    }
+}
+
+#[test]
+fn component_is_mounted_after_rendering_to_string() {
+    set_render_mode(RenderMode::Immediate);
+    let element = document().create_element("div").unwrap();
+    App::<Prerendered>::new().mount(element.clone());
+
+    RENDERED.with(|rendered| assert_eq!(*rendered.borrow(), "<span>label</span>"));
+    assert_eq!(element.text_content(), Some("label".to_owned()));
+}
@@ -1,6 +1,10 @@
 #![recursion_limit = "128"]
+use stdweb::web::{document, IElement, INode, IParentNode};
 #[cfg(feature = "wasm-bindgen-test")]
 use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+use yew::app::App;
+use yew::scheduler::{set_render_mode, RenderMode};
+use yew::virtual_dom::vtag::VTag;
 use yew::virtual_dom::VNode;
 use yew::{html, Component, ComponentLink, Html, Renderable, ShouldRender};
 
@@ -82,6 +86,19 @@ fn it_compares_attributes() {
     assert_ne!(a, c);
 }
 
+#[test]
+fn it_compares_static_and_owned_attribute_names() {
+    let a: VNode<Comp> = html! {
+        <div a="test"></div>
+    };
+
+    let mut vtag = VTag::new("div");
+    vtag.add_attribute(&String::from("a"), &"test");
+    let b: VNode<Comp> = VNode::VTag(vtag);
+
+    assert_eq!(a, b);
+}
+
 #[test]
 fn it_compares_children() {
     let a: VNode<Comp> = html! {
@@ -258,3 +275,79 @@ fn it_allows_aria_attributes() {
         panic!("vtag expected");
     }
 }
+
+#[test]
+fn it_renders_to_html_string() {
+    let a: VNode<Comp> = html! {
+        <div id="main" class="b a">
+            <p title="\"quoted\"">{ "1 < 2 & 3" }</p>
+            <input type="checkbox" checked=true />
+            <br />
+        </div>
+    };
+    assert_eq!(
+        a.to_html_string(),
+        concat!(
+            r#"<div class="a b" id="main">"#,
+            r#"<p title="&quot;quoted&quot;">1 &lt; 2 &amp; 3</p>"#,
+            r#"<input checked="" type="checkbox">"#,
+            r#"<br>"#,
+            r#"</div>"#,
+        )
+    );
+}
+
+struct Label {
+    text: String,
+}
+
+impl Component for Label {
+    type Message = String;
+    type Properties = ();
+
+    fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
+        Label {
+            text: "a".to_string(),
+        }
+    }
+
+    fn update(&mut self, text: Self::Message) -> ShouldRender {
+        self.text = text;
+        true
+    }
+}
+
+impl Renderable<Label> for Label {
+    fn view(&self) -> Html<Self> {
+        html! {
+            <p title=&self.text>{ &self.text }</p>
+        }
+    }
+}
+
+#[test]
+fn it_skips_writes_of_equal_attributes_and_text() {
+    set_render_mode(RenderMode::Immediate);
+    let element = document().create_element("div").unwrap();
+    let mut scope = App::<Label>::new().mount(element.clone());
+    let p = element.query_selector("p").unwrap().expect("p is rendered");
+    p.set_attribute("title", "changed").unwrap();
+    p.first_child().unwrap().set_node_value(Some("changed"));
+
+    scope.send_message("a".to_string());
+    assert_eq!(
+        p.get_attribute("title").as_ref().map(String::as_str),
+        Some("changed")
+    );
+    assert_eq!(
+        p.text_content().as_ref().map(String::as_str),
+        Some("changed")
+    );
+
+    scope.send_message("b".to_string());
+    assert_eq!(
+        p.get_attribute("title").as_ref().map(String::as_str),
+        Some("b")
+    );
+    assert_eq!(p.text_content().as_ref().map(String::as_str), Some("b"));
+}
@@ -0,0 +1,18 @@
+use std::time::Duration;
+#[cfg(feature = "wasm-bindgen-test")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+use yew::services::websocket::Reconnect;
+
+#[test]
+fn reconnect_delay_grows_to_the_limit() {
+    let reconnect = Reconnect {
+        initial: Duration::from_millis(500),
+        max: Duration::from_secs(3),
+        factor: 2.0,
+    };
+    assert_eq!(reconnect.delay(0), Duration::from_millis(500));
+    assert_eq!(reconnect.delay(1), Duration::from_secs(1));
+    assert_eq!(reconnect.delay(2), Duration::from_secs(2));
+    assert_eq!(reconnect.delay(3), Duration::from_secs(3));
+    assert_eq!(reconnect.delay(10), Duration::from_secs(3));
+}